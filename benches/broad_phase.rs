@@ -0,0 +1,94 @@
+use collision_detection::{geometry::Rectangle, quad_tree::QuadTree};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const WIDTH: f64 = 1920.0;
+const HEIGHT: f64 = 1080.0;
+
+/// Square AABBs between 1 and 100 wide, spread evenly over the screen.
+fn uniform(count: usize) -> Vec<Rectangle> {
+    let mut rng = StdRng::seed_from_u64(1);
+    (0..count)
+        .map(|_| {
+            let size = rng.gen_range(1.0, 100.0);
+            Rectangle::new(
+                rng.gen_range(0.0, WIDTH - size),
+                rng.gen_range(0.0, HEIGHT - size),
+                size,
+                size,
+            )
+        })
+        .collect()
+}
+
+/// The same sizes packed into a few tight clusters, which is what pushes
+/// the quad tree deepest.
+fn clustered(count: usize) -> Vec<Rectangle> {
+    let mut rng = StdRng::seed_from_u64(2);
+    let centers: Vec<(f64, f64)> = (0..8)
+        .map(|_| {
+            (
+                rng.gen_range(200.0, WIDTH - 200.0),
+                rng.gen_range(200.0, HEIGHT - 200.0),
+            )
+        })
+        .collect();
+    (0..count)
+        .map(|k| {
+            let size = rng.gen_range(1.0, 100.0);
+            let (x, y) = centers[k % centers.len()];
+            Rectangle::new(
+                x + rng.gen_range(-150.0, 150.0),
+                y + rng.gen_range(-150.0, 150.0),
+                size,
+                size,
+            )
+        })
+        .collect()
+}
+
+fn build(aabbs: &[Rectangle]) -> QuadTree {
+    let mut tree = QuadTree::new(Rectangle::new(0.0, 0.0, WIDTH, HEIGHT));
+    for (id, aabb) in aabbs.iter().enumerate() {
+        tree.insert(id, *aabb);
+    }
+    tree
+}
+
+fn distributions(count: usize) -> [(&'static str, Vec<Rectangle>); 2] {
+    [("uniform", uniform(count)), ("clustered", clustered(count))]
+}
+
+fn quad_tree_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("quad_tree_build");
+    for count in [500, 1500, 4500] {
+        for (name, aabbs) in distributions(count) {
+            group.bench_with_input(BenchmarkId::new(name, count), &aabbs, |b, aabbs| {
+                b.iter(|| build(black_box(aabbs)))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn quad_tree_query(c: &mut Criterion) {
+    let mut group = c.benchmark_group("quad_tree_query_all");
+    for count in [500, 1500, 4500] {
+        for (name, aabbs) in distributions(count) {
+            let tree = build(&aabbs);
+            group.bench_with_input(BenchmarkId::new(name, count), &aabbs, |b, aabbs| {
+                b.iter(|| {
+                    aabbs
+                        .iter()
+                        .enumerate()
+                        .map(|(id, aabb)| tree.might_collide(id, *aabb).count())
+                        .sum::<usize>()
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, quad_tree_build, quad_tree_query);
+criterion_main!(benches);
@@ -0,0 +1,75 @@
+use collision_detection::{
+    collisions::{
+        collide_circle_rect, collide_cirle_circle, collide_poly_circle, collide_poly_poly,
+        collide_poly_rect, collide_rect_rect,
+    },
+    geometry::{project_polygon_lanes_scalar, Circle, Polygon, Rectangle, Vector, LANES},
+    polyhedron::PolyhedronObject,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn projection(c: &mut Criterion) {
+    let poly = PolyhedronObject::new(30.0, 10);
+    let polygon = Polygon::new(Vector::new(100.0, 100.0), poly.points());
+    let lines: Vec<Rectangle> = polygon.lines().take(LANES).collect();
+
+    let mut group = c.benchmark_group("project_10_points_on_4_axes");
+    group.bench_function("scalar", |b| {
+        b.iter(|| project_polygon_lanes_scalar(black_box(&lines), black_box(&polygon)))
+    });
+    #[cfg(feature = "simd")]
+    group.bench_function("simd", |b| {
+        b.iter(|| {
+            collision_detection::geometry::project_polygon_lanes_simd(
+                black_box(&lines),
+                black_box(&polygon),
+            )
+        })
+    });
+    group.finish();
+}
+
+fn poly_poly(c: &mut Criterion) {
+    let a = PolyhedronObject::new(30.0, 10);
+    let b = PolyhedronObject::new(25.0, 10);
+
+    c.bench_function("collide_poly_poly_10_10", |bench| {
+        bench.iter(|| {
+            collide_poly_poly(
+                &a.at(black_box(Vector::new(100.0, 100.0))),
+                &b.at(black_box(Vector::new(140.0, 110.0))),
+            )
+        })
+    });
+}
+
+/// Every pair overlaps, so no axis exits early.
+fn collide(c: &mut Criterion) {
+    let rect_a = Rectangle::new(80.0, 80.0, 50.0, 40.0);
+    let rect_b = Rectangle::new(110.0, 100.0, 40.0, 40.0);
+    let circle_a = Circle::new(Vector::new(100.0, 100.0), 25.0);
+    let circle_b = Circle::new(Vector::new(130.0, 110.0), 20.0);
+    let poly = PolyhedronObject::new(30.0, 6);
+    let polygon = poly.at(Vector::new(100.0, 100.0));
+
+    let mut group = c.benchmark_group("collide");
+    group.bench_function("rect_rect", |b| {
+        b.iter(|| collide_rect_rect(black_box(&rect_a), black_box(&rect_b)))
+    });
+    group.bench_function("circle_circle", |b| {
+        b.iter(|| collide_cirle_circle(black_box(&circle_a), black_box(&circle_b)))
+    });
+    group.bench_function("circle_rect", |b| {
+        b.iter(|| collide_circle_rect(black_box(&circle_b), black_box(&rect_a)))
+    });
+    group.bench_function("poly_rect", |b| {
+        b.iter(|| collide_poly_rect(black_box(&polygon), black_box(&rect_b)))
+    });
+    group.bench_function("poly_circle", |b| {
+        b.iter(|| collide_poly_circle(black_box(&polygon), black_box(&circle_b)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, projection, poly_poly, collide);
+criterion_main!(benches);
@@ -0,0 +1,20 @@
+use collision_detection::world::World;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+/// One tick of the 4500-object demo scene on a full HD canvas, seeded so runs
+/// compare. Every sample starts from a fresh scene rather than a settled one.
+fn tick(c: &mut Criterion) {
+    let mut group = c.benchmark_group("world");
+    group.sample_size(10);
+    group.bench_function("tick_4500", |b| {
+        b.iter_batched(
+            || World::new_seeded(1920.0, 1080.0, 1),
+            |mut world| world.tick(black_box(1.0 / 60.0)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, tick);
+criterion_main!(benches);
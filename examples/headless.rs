@@ -0,0 +1,45 @@
+//! Runs the demo scene without a browser and prints what every tick did.
+//!
+//! cargo run --release --features timings --example headless -- [ticks] [seed]
+
+use collision_detection::{stats::StepStats, world::World};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let ticks: usize = args.next().map_or(60, |it| it.parse().expect("ticks"));
+    let seed: u64 = args.next().map_or(1, |it| it.parse().expect("seed"));
+
+    let mut world = World::new_seeded(1920.0, 1080.0, seed);
+    let mut total = StepStats::default();
+    for tick in 0..ticks {
+        let stats = world.tick(1.0 / 60.0);
+        println!(
+            "{:>5} candidates {:>7} rejected {:>7} tested {:>7} contacts {:>6} depth {:>2} nodes {:>6} {:>8.3}ms",
+            tick,
+            stats.candidates,
+            stats.aabb_rejections,
+            stats.narrow_tests,
+            stats.contacts,
+            stats.tree_depth,
+            stats.tree_nodes,
+            stats.timings.total() * 1000.0,
+        );
+        total.candidates += stats.candidates;
+        total.contacts += stats.contacts;
+        total.timings.broad += stats.timings.broad;
+        total.timings.narrow += stats.timings.narrow;
+        total.timings.response += stats.timings.response;
+        total.timings.integrate += stats.timings.integrate;
+    }
+
+    let ticks = ticks.max(1) as f64;
+    println!(
+        "mean: candidates {:.0} contacts {:.0} broad {:.3}ms narrow {:.3}ms response {:.3}ms integrate {:.3}ms",
+        total.candidates as f64 / ticks,
+        total.contacts as f64 / ticks,
+        total.timings.broad * 1000.0 / ticks,
+        total.timings.narrow * 1000.0 / ticks,
+        total.timings.response * 1000.0 / ticks,
+        total.timings.integrate * 1000.0 / ticks,
+    );
+}
@@ -0,0 +1,62 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use collision_detection::{
+    circle::CircleObject, geometry::Vector, object::MetaObject, polyhedron::PolyhedronObject,
+    rectangle::RectangleObject,
+};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Arbitrary, Debug)]
+enum Shape {
+    Rect(f64, f64),
+    Circle(f64),
+    Poly(f64, u8),
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    a: Shape,
+    a_position: (f64, f64),
+    b: Shape,
+    b_position: (f64, f64),
+}
+
+/// Keeps values finite and small enough that their squares are too.
+fn clamp(value: f64) -> f64 {
+    if value.is_finite() {
+        value.clamp(-1e6, 1e6)
+    } else {
+        0.0
+    }
+}
+
+fn size(value: f64) -> f64 {
+    clamp(value).abs().max(1e-3)
+}
+
+fn build(shape: &Shape) -> MetaObject {
+    match *shape {
+        Shape::Rect(w, h) => RectangleObject::new_obj(size(w), size(h)),
+        Shape::Circle(radius) => CircleObject::new_obj(size(radius)),
+        Shape::Poly(radius, points) => {
+            let points = 3 + points as usize % 22;
+            PolyhedronObject::new_obj(size(radius), points)
+        }
+    }
+}
+
+fn position((x, y): (f64, f64)) -> Vector {
+    Vector::new(clamp(x), clamp(y))
+}
+
+fuzz_target!(|input: Input| {
+    let (a, b) = (build(&input.a), build(&input.b));
+    let (pa, pb) = (position(input.a_position), position(input.b_position));
+
+    let ab = a.collides_with(pa, &b, pb);
+    let ba = b.collides_with(pb, &a, pa);
+    for vector in ab.iter().chain(ba.iter()) {
+        assert!(vector.x.is_finite() && vector.y.is_finite(), "{:?}", input);
+    }
+});
@@ -0,0 +1,59 @@
+/// Names a body in a `World`. A handle outlives its body: once the body is
+/// removed the slot gets a new generation and the old handle stops matching.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BodyHandle {
+    pub index: usize,
+    pub generation: u32,
+}
+
+/// How a body takes part in the simulation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BodyType {
+    /// Never moves; level geometry.
+    Static,
+    /// Moves only by the speed it is given and pushes dynamic bodies without
+    /// being pushed back; moving platforms.
+    Kinematic,
+    /// Moved by gravity, forces and contacts.
+    #[default]
+    Dynamic,
+}
+
+/// Surface properties used by the collision response. Friction of two
+/// touching bodies is combined by its geometric mean, restitution by the
+/// larger of the two.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Material {
+    /// Up to this ratio of the normal impulse, sliding is stopped entirely.
+    pub static_friction: f64,
+    /// Ratio of the normal impulse that slows sliding once it breaks loose.
+    pub dynamic_friction: f64,
+    /// Share of the closing speed kept after a bounce: 0 stops dead, 1 is
+    /// perfectly elastic.
+    pub restitution: f64,
+}
+
+impl Material {
+    pub fn new(static_friction: f64, dynamic_friction: f64) -> Self {
+        Self {
+            static_friction,
+            dynamic_friction,
+            restitution: 1.0,
+        }
+    }
+
+    pub fn with_restitution(self, restitution: f64) -> Self {
+        Self {
+            restitution,
+            ..self
+        }
+    }
+}
+
+/// Frictionless and elastic, so bodies keep sliding and bouncing like they
+/// always did.
+impl Default for Material {
+    fn default() -> Self {
+        Self::new(0.0, 0.0)
+    }
+}
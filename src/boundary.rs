@@ -0,0 +1,43 @@
+use crate::geometry::{Rectangle, Vector};
+
+/// What happens to bodies at the edges of the world's `size` box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Boundary {
+    /// Dynamic bodies bounce back in, keeping `restitution` of their speed.
+    Walls { restitution: f64 },
+    /// Bodies leaving one side come back in on the other, and touch bodies
+    /// across the edge as if it were not there.
+    Wrap,
+    /// Bodies that have left the box entirely are removed and show up in
+    /// `World::drain_exited`.
+    Kill,
+    /// Bodies go anywhere; the quad tree grows to follow them.
+    Open,
+}
+
+impl Default for Boundary {
+    fn default() -> Self {
+        Boundary::Walls { restitution: 1.0 }
+    }
+}
+
+/// Shifts that carry the parts of `aabb` sticking out of a wrapped `size`
+/// box to the opposite side.
+pub(crate) fn wrap_shifts(aabb: Rectangle, size: Vector) -> Vec<Vector> {
+    let shifts = |low: f64, high: f64, size: f64| {
+        let mut shifts = vec![0.0];
+        if low < 0.0 {
+            shifts.push(size);
+        }
+        if high > size {
+            shifts.push(-size);
+        }
+        shifts
+    };
+    let xs = shifts(aabb.left(), aabb.right(), size.x);
+    let ys = shifts(aabb.top(), aabb.bottom(), size.y);
+    xs.iter()
+        .flat_map(|&x| ys.iter().map(move |&y| Vector::new(x, y)))
+        .filter(|shift| *shift != Vector::zero())
+        .collect()
+}
@@ -0,0 +1,163 @@
+use crate::{
+    events::ShapeHit, filter::CollisionFilter, geometry::Vector, object::MetaObject, world::World,
+};
+
+/// Moves a shape through a `World` with shape casts instead of the dynamic
+/// response: it slides along whatever it hits, walks up slopes no steeper than
+/// `max_slope`, steps onto ledges up to `step_height` and knows whether it is
+/// standing on something. The controller is not a body itself; to let dynamic
+/// bodies bump into it, mirror it with a kinematic body sharing its filter
+/// group. Its shape can be a rectangle, circle or polygon; there is no
+/// capsule shape.
+#[derive(Clone)]
+pub struct CharacterController {
+    pub shape: MetaObject,
+    pub position: Vector,
+    /// Away from the ground, opposite to gravity.
+    pub up: Vector,
+    /// Steepest slope, in radians, still walked on as ground.
+    pub max_slope: f64,
+    pub step_height: f64,
+    /// Gap kept to every surface so the next cast starts clear of it.
+    pub skin: f64,
+    pub filter: CollisionFilter,
+    ground: Option<ShapeHit>,
+}
+
+impl CharacterController {
+    pub fn new(shape: MetaObject, position: Vector) -> Self {
+        Self {
+            shape,
+            position,
+            up: Vector::new(0.0, -1.0),
+            max_slope: std::f64::consts::FRAC_PI_4,
+            step_height: 0.0,
+            skin: 0.1,
+            filter: CollisionFilter::default(),
+            ground: None,
+        }
+    }
+
+    pub fn is_grounded(&self) -> bool {
+        self.ground.is_some()
+    }
+
+    /// The surface stood on after the last move.
+    pub fn ground(&self) -> Option<ShapeHit> {
+        self.ground
+    }
+
+    /// Moves by up to `motion` and returns how far the controller really went.
+    /// The part of `motion` along `up` is applied after the sideways part, so
+    /// gravity pulled in every frame keeps the character on the ground.
+    pub fn move_by(&mut self, world: &World, motion: Vector) -> Vector {
+        let start = self.position;
+        let was_grounded = self.is_grounded();
+        let vertical = self.up * motion.dot(&self.up);
+        let lateral = motion - vertical;
+
+        let (mut position, wall) = self.slide(world, start, lateral, false);
+        if wall.is_some() && was_grounded && self.step_height > 0.0 {
+            if let Some(stepped) = self.step(world, start, lateral) {
+                if self.progress(stepped - start, lateral)
+                    > self.progress(position - start, lateral)
+                {
+                    position = stepped;
+                }
+            }
+        }
+        position = self.slide(world, position, vertical, true).0;
+
+        if was_grounded && motion.dot(&self.up) <= 0.0 {
+            position = self.snap(world, position, self.step_height + self.skin);
+        }
+        self.ground = self
+            .cast(world, position, -self.up * (self.skin * 2.0))
+            .filter(|hit| self.walkable(hit.normal));
+        self.position = position;
+        position - start
+    }
+
+    fn walkable(&self, normal: Vector) -> bool {
+        normal.dot(&self.up) >= self.max_slope.cos() - 0.000001
+    }
+
+    fn progress(&self, moved: Vector, lateral: Vector) -> f64 {
+        moved.dot(&lateral.try_norm().unwrap_or_else(Vector::zero))
+    }
+
+    fn cast(&self, world: &World, from: Vector, motion: Vector) -> Option<ShapeHit> {
+        world.cast_shape(&self.shape, from, motion, &self.filter)
+    }
+
+    /// Moves along `motion`, stopping `skin` short of each hit and carrying on
+    /// along the surface. Steep surfaces only stop the motion into them and
+    /// never lift the character; on ground, `land` drops what is left.
+    /// Returns where it ended and the last steep surface it ran into.
+    fn slide(
+        &self,
+        world: &World,
+        from: Vector,
+        motion: Vector,
+        land: bool,
+    ) -> (Vector, Option<ShapeHit>) {
+        let mut position = from;
+        let mut remaining = motion;
+        let mut wall = None;
+        for _ in 0..4 {
+            let len = remaining.len();
+            if len < 0.000001 {
+                break;
+            }
+            let Some(hit) = self.cast(world, position, remaining) else {
+                position += remaining;
+                break;
+            };
+            let travel = (hit.fraction * len - self.skin).max(0.0);
+            position += remaining * (travel / len);
+            remaining = remaining * (1.0 - travel / len);
+
+            let walkable = self.walkable(hit.normal);
+            if walkable && land {
+                break;
+            }
+            let mut normal = hit.normal;
+            if !walkable {
+                wall = Some(hit);
+                let along = remaining - normal * remaining.dot(&normal);
+                if along.dot(&self.up) > 0.0 {
+                    let flat = normal - self.up * normal.dot(&self.up);
+                    normal = flat.try_norm().unwrap_or(normal);
+                }
+            }
+            let into = remaining.dot(&normal);
+            if into < 0.0 {
+                remaining -= normal * into;
+            }
+        }
+        (position, wall)
+    }
+
+    /// Lifts by `step_height`, moves sideways and drops back down. Fails if
+    /// that lands on something too steep to stand on.
+    fn step(&self, world: &World, from: Vector, lateral: Vector) -> Option<Vector> {
+        let raised = self.slide(world, from, self.up * self.step_height, false).0;
+        let (across, _) = self.slide(world, raised, lateral, false);
+        let drop = (raised - from).dot(&self.up) + self.skin;
+        match self.cast(world, across, -self.up * drop) {
+            Some(hit) if !self.walkable(hit.normal) => None,
+            _ => Some(self.snap(world, across, drop)),
+        }
+    }
+
+    /// Moves down by up to `distance` onto walkable ground, or stays put if
+    /// there is none that close.
+    fn snap(&self, world: &World, from: Vector, distance: f64) -> Vector {
+        match self.cast(world, from, -self.up * distance) {
+            Some(hit) if self.walkable(hit.normal) => {
+                from - self.up * (hit.fraction * distance - self.skin).max(0.0)
+            }
+            _ => from,
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    character::CharacterController, geometry::Vector, polyhedron::PolyhedronObject,
+    rectangle::RectangleObject, world::World, world_test::add_static,
+};
+
+/// A 1000 wide floor whose top is at y = 500.
+fn floor() -> World {
+    let mut world = World::empty(1000.0, 1000.0);
+    add_static(
+        &mut world,
+        RectangleObject::new_obj(1000.0, 100.0),
+        Vector::new(500.0, 550.0),
+    );
+    world
+}
+
+fn walk(world: &World, character: &mut CharacterController, speed: f64, frames: usize) {
+    for _ in 0..frames {
+        character.move_by(world, Vector::new(speed, 5.0));
+    }
+}
+
+#[test]
+fn test_character_lands_and_slides_along_walls() {
+    let mut world = floor();
+    let wall = add_static(
+        &mut world,
+        RectangleObject::new_obj(20.0, 400.0),
+        Vector::new(600.0, 300.0),
+    );
+    let mut character = CharacterController::new(
+        RectangleObject::new_obj(20.0, 40.0),
+        Vector::new(400.0, 100.0),
+    );
+    assert!(!character.is_grounded());
+
+    let moved = character.move_by(&world, Vector::new(0.0, 1000.0));
+    assert!((moved.y - 380.0).abs() < 0.5, "{:?}", moved);
+    assert!(character.is_grounded());
+
+    character.position.y -= 200.0;
+    character.move_by(&world, Vector::new(0.0, 0.0));
+    assert!(!character.is_grounded());
+    let moved = character.move_by(&world, Vector::new(500.0, 100.0));
+    assert!(
+        (character.position.x - 580.0).abs() < 0.5,
+        "{:?}",
+        character.position
+    );
+    assert!((moved.y - 100.0).abs() < 1e-6, "{:?}", moved);
+    walk(&world, &mut character, 0.0, 40);
+    assert!(character.is_grounded());
+    assert_ne!(character.ground().unwrap().body, wall);
+}
+
+#[test]
+fn test_character_steps_over_low_obstacles_only() {
+    let run = |height: f64| {
+        let mut world = floor();
+        add_static(
+            &mut world,
+            RectangleObject::new_obj(40.0, height),
+            Vector::new(500.0, 500.0 - height / 2.0),
+        );
+        let mut character = CharacterController::new(
+            RectangleObject::new_obj(20.0, 40.0),
+            Vector::new(400.0, 479.0),
+        );
+        character.step_height = 10.0;
+        walk(&world, &mut character, 4.0, 50);
+        character
+    };
+
+    let low = run(6.0);
+    assert!(low.position.x > 560.0, "{:?}", low.position);
+    assert!(low.is_grounded());
+
+    let high = run(20.0);
+    assert!((high.position.x - 469.9).abs() < 0.5, "{:?}", high.position);
+    assert!(high.is_grounded());
+}
+
+#[test]
+fn test_character_climbs_gentle_slopes_only() {
+    // A hexagon sunk to its waist makes a hill with 30 degree sides.
+    let run = |max_slope: f64| {
+        let mut world = floor();
+        add_static(
+            &mut world,
+            PolyhedronObject::new_obj(100.0, 6),
+            Vector::new(600.0, 550.0),
+        );
+        let mut character = CharacterController::new(
+            RectangleObject::new_obj(20.0, 40.0),
+            Vector::new(400.0, 479.0),
+        );
+        character.max_slope = max_slope.to_radians();
+        walk(&world, &mut character, 4.0, 40);
+        character
+    };
+
+    let climber = run(45.0);
+    assert!(climber.position.y < 460.0, "{:?}", climber.position);
+    assert!(climber.is_grounded());
+
+    let blocked = run(20.0);
+    assert!(blocked.position.x < 520.0, "{:?}", blocked.position);
+    assert!(
+        (blocked.position.y - 479.9).abs() < 0.5,
+        "{:?}",
+        blocked.position
+    );
+}
@@ -1,54 +1,37 @@
 use crate::{
-    collisions::{collide_circle_rect, collide_cirle_circle, collide_poly_circle},
-    geometry::{Rectangle, Vector},
-    object::MetaObject,
+    geometry::{Circle, Rectangle, Vector},
+    object::{MetaObject, Placed},
+    scalar::Scalar,
 };
 
-pub struct CircleObject {
-    pub center: Vector,
-    pub radius: f64,
-    pub speed: Vector,
+#[derive(Clone)]
+pub struct CircleObject<S = f64> {
+    pub radius: S,
 }
 
-impl CircleObject {
-    pub fn new(center: Vector, radius: f64, speed: Vector) -> Self {
-        Self {
-            center,
-            radius,
-            speed,
-        }
+impl<S: Scalar> CircleObject<S> {
+    pub fn new(radius: S) -> Self {
+        Self { radius }
     }
 
-    pub fn new_obj(center: Vector, radius: f64, speed: Vector) -> MetaObject {
-        MetaObject::Circle(Self::new(center, radius, speed))
+    pub fn new_obj(radius: S) -> MetaObject<S> {
+        MetaObject::Circle(Self::new(radius))
     }
 
-    pub fn tick(&mut self, delta_time: f64) {
-        self.center += self.speed() * delta_time;
+    pub fn at(&self, position: Vector<S>) -> Circle<S> {
+        Circle::new(position, self.radius)
     }
 
-    pub fn collides_with(&self, other: &MetaObject) -> Option<Vector> {
-        match other {
-            MetaObject::Circle(circle) => collide_cirle_circle(self, circle),
-            MetaObject::Rect(rect) => collide_circle_rect(self, rect),
-            MetaObject::Poly(poly) => collide_poly_circle(poly, self).map(|it| it * -1.0),
-        }
+    pub fn collides_with(
+        &self,
+        position: Vector<S>,
+        other: &MetaObject<S>,
+        other_position: Vector<S>,
+    ) -> Option<Vector<S>> {
+        Placed::Circle(self.at(position)).collides_with(&other.at(other_position))
     }
 
-    pub fn speed(&self) -> Vector {
-        self.speed
-    }
-
-    pub fn mov(&mut self, direction: Vector) {
-        self.center += direction;
-    }
-
-    pub fn kick(&mut self, speed: Vector) {
-        self.speed += speed
-    }
-
-    pub fn aabb(&self) -> Rectangle {
-        let rad = Vector::new(self.radius, self.radius);
-        Rectangle::new_vec(self.center - rad, rad * 2.0)
+    pub fn aabb(&self) -> Rectangle<S> {
+        self.at(Vector::zero()).aabb()
     }
 }
@@ -1,145 +1,206 @@
 use std::iter;
 
 use crate::{
-    circle::CircleObject,
-    geometry::{project_circle, project_points, Rectangle, Vector},
-    polyhedron::PolyhedronObject,
-    rectangle::RectangleObject,
+    geometry::{
+        project_circle, project_points, project_polygon_lanes, Circle, Polygon, Rectangle, Vector,
+        LANES,
+    },
+    scalar::Scalar,
 };
 
-pub fn collide_rect_rect(a: &RectangleObject, b: &RectangleObject) -> Option<Vector> {
-    let mut min = f64::MAX;
-    let mut res = None;
-
-    let rect_line_x = Rectangle::new_vec(b.shape.coord, Vector::new(1.0, 0.0));
-    let rect_line_y = Rectangle::new_vec(b.shape.coord, Vector::new(0.0, 1.0));
-
-    for line in [rect_line_x, rect_line_y].iter() {
-        let a_proj = project_points(line, a.shape.points());
-        let b_proj = project_points(line, b.shape.points());
+/// How two intervals on a separating axis relate to each other.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Overlap<S = f64> {
+    Separated,
+    /// Signed distance that moves the second interval out of the first the
+    /// shorter way.
+    Penetration(S),
+}
 
-        let c = one_dimensional_collision(b_proj, a_proj);
+/// Keeps the shortest push-out vector seen over a set of axes.
+struct MinAxis<S> {
+    min: Option<S>,
+    res: Option<Vector<S>>,
+}
 
-        if c.is_nan() {
-            return None;
-        }
-        if c.abs() < min {
-            min = c.abs();
-            res = Some(line.size * c);
+impl<S: Scalar> MinAxis<S> {
+    fn new() -> Self {
+        Self {
+            min: None,
+            res: None,
         }
     }
-    res
-}
 
-pub fn collide_cirle_circle(a: &CircleObject, b: &CircleObject) -> Option<Vector> {
-    let d = (a.center - b.center).len();
-    let shift_len = a.radius + b.radius - d;
-    if shift_len > 0.0 {
-        return Some((b.center - a.center) * (shift_len / d));
+    /// Returns `false` once the axis separates the shapes.
+    fn add(&mut self, axis: Vector<S>, overlap: Overlap<S>) -> bool {
+        match overlap {
+            Overlap::Separated => return false,
+            Overlap::Penetration(c) => {
+                if self.min.is_none_or(|min| c.abs() < min) {
+                    self.min = Some(c.abs());
+                    self.res = Some(axis * c);
+                }
+            }
+        }
+        true
     }
-    None
 }
 
-pub fn collide_circle_rect(a: &CircleObject, b: &RectangleObject) -> Option<Vector> {
-    let mut min = f64::MAX;
-    let mut res = None;
+/// The axis to push along when two centres coincide and no direction is
+/// better than another.
+fn fallback_axis<S: Scalar>() -> Vector<S> {
+    Vector::new(S::ONE, S::ZERO)
+}
 
-    let rect_line_x = Rectangle::new_vec(b.shape.coord, Vector::new(1.0, 0.0));
-    let rect_line_y = Rectangle::new_vec(b.shape.coord, Vector::new(0.0, 1.0));
-    let circle_line = Rectangle::new_vec(a.center, (b.center() - a.center).norm());
+pub fn collide_rect_rect<S: Scalar>(a: &Rectangle<S>, b: &Rectangle<S>) -> Option<Vector<S>> {
+    let mut res = MinAxis::new();
 
-    for line in [rect_line_x, rect_line_y, circle_line].iter() {
-        let a_proj = project_circle(line, a.center, a.radius);
-        let b_proj = project_points(line, b.shape.points());
+    let rect_line_x = Rectangle::new_vec(b.coord, Vector::new(S::ONE, S::ZERO));
+    let rect_line_y = Rectangle::new_vec(b.coord, Vector::new(S::ZERO, S::ONE));
 
-        let c = one_dimensional_collision(b_proj, a_proj);
+    for line in [rect_line_x, rect_line_y].iter() {
+        let a_proj = project_points(line, a.points());
+        let b_proj = project_points(line, b.points());
 
-        if c.is_nan() {
+        if !res.add(line.size, one_dimensional_collision(b_proj, a_proj)) {
             return None;
         }
-        if c.abs() < min {
-            min = c.abs();
-            res = Some(line.size * c);
-        }
     }
-    res
+    res.res
 }
 
-pub fn collide_poly_rect(a: &PolyhedronObject, b: &RectangleObject) -> Option<Vector> {
-    let mut min = f64::MAX;
-    let mut res = None;
+pub fn collide_cirle_circle<S: Scalar>(a: &Circle<S>, b: &Circle<S>) -> Option<Vector<S>> {
+    let d = (a.center - b.center).len();
+    let shift_len = a.radius + b.radius - d;
+    if shift_len > S::ZERO {
+        if d > S::ZERO {
+            return Some((b.center - a.center) * (shift_len / d));
+        }
+        return Some(fallback_axis() * shift_len);
+    }
+    None
+}
 
-    let rect_line_x = Rectangle::new_vec(b.shape.coord, Vector::new(1.0, 0.0));
-    let rect_line_y = Rectangle::new_vec(b.shape.coord, Vector::new(0.0, 1.0));
+pub fn collide_circle_rect<S: Scalar>(a: &Circle<S>, b: &Rectangle<S>) -> Option<Vector<S>> {
+    let mut res = MinAxis::new();
 
-    for line in a.lines().chain(iter::once(rect_line_x)).chain(iter::once(rect_line_y)) {
-        let a_proj = project_points(&line, a.points());
-        let b_proj = project_points(&line, b.shape.points());
+    let rect_line_x = Rectangle::new_vec(b.coord, Vector::new(S::ONE, S::ZERO));
+    let rect_line_y = Rectangle::new_vec(b.coord, Vector::new(S::ZERO, S::ONE));
+    let circle_axis = (closest_point(b.points(), a.center) - a.center)
+        .try_norm()
+        .unwrap_or_else(fallback_axis);
+    let circle_line = Rectangle::new_vec(a.center, circle_axis);
 
-        let c = one_dimensional_collision(b_proj, a_proj);
+    for line in [rect_line_x, rect_line_y, circle_line].iter() {
+        let a_proj = project_circle(line, a.center, a.radius);
+        let b_proj = project_points(line, b.points());
 
-        if c.is_nan() {
+        if !res.add(line.size, one_dimensional_collision(b_proj, a_proj)) {
             return None;
         }
-        if c.abs() < min {
-            min = c.abs();
-            res = Some(line.size * c);
-        }
     }
-    res
+    res.res
 }
 
-pub fn collide_poly_circle(a: &PolyhedronObject, b: &CircleObject) -> Option<Vector> {
-    let mut min = f64::MAX;
-    let mut res = None;
-    let circle_line = Rectangle::new_vec(a.center, (b.center - a.center).norm());
-
-    for line in a.lines().chain(iter::once(circle_line)) {
-        let a_proj = project_points(&line, a.points());
-        let b_proj = project_circle(&line, b.center, b.radius);
+pub fn collide_poly_rect<S: Scalar>(a: &Polygon<S>, b: &Rectangle<S>) -> Option<Vector<S>> {
+    let rect_line_x = Rectangle::new_vec(b.coord, Vector::new(S::ONE, S::ZERO));
+    let rect_line_y = Rectangle::new_vec(b.coord, Vector::new(S::ZERO, S::ONE));
+
+    let corners = [
+        Vector::zero(),
+        Vector::new(b.size.x, S::ZERO),
+        b.size,
+        Vector::new(S::ZERO, b.size.y),
+    ];
+    let b_poly = Polygon::new(b.coord, &corners);
+
+    let lines = a
+        .lines()
+        .chain(iter::once(rect_line_x))
+        .chain(iter::once(rect_line_y));
+    collide_polygon_on_lines(lines, a, |lines| project_polygon_lanes(lines, &b_poly))
+}
 
-        let c = one_dimensional_collision(b_proj, a_proj);
-        if c.is_nan() {
-            return None;
-        }
-        if c.abs() < min {
-            min = c.abs();
-            res = Some(line.size * c);
+pub fn collide_poly_circle<S: Scalar>(a: &Polygon<S>, b: &Circle<S>) -> Option<Vector<S>> {
+    let circle_axis = (closest_point(a.points(), b.center) - b.center)
+        .try_norm()
+        .unwrap_or_else(fallback_axis);
+    let circle_line = Rectangle::new_vec(a.center, circle_axis);
+
+    let lines = a.lines().chain(iter::once(circle_line));
+    collide_polygon_on_lines(lines, a, |lines| {
+        let mut res = [Vector::zero(); LANES];
+        for (k, line) in lines.iter().enumerate() {
+            res[k] = project_circle(line, b.center, b.radius);
         }
-    }
-    res
+        res
+    })
 }
 
-pub fn collide_poly_poly(a: &PolyhedronObject, b: &PolyhedronObject) -> Option<Vector> {
-    let mut min = f64::MAX;
-    let mut res = None;
-
-    for line in a.lines().chain(b.lines()) {
-        let a_proj = project_points(&line, a.points());
-        let b_proj = project_points(&line, b.points());
+pub fn collide_poly_poly<S: Scalar>(a: &Polygon<S>, b: &Polygon<S>) -> Option<Vector<S>> {
+    let lines = a.lines().chain(b.lines());
+    collide_polygon_on_lines(lines, a, |lines| project_polygon_lanes(lines, b))
+}
 
-        let c = one_dimensional_collision(b_proj, a_proj);
-        if c.is_nan() {
-            return None;
+/// SAT over `lines`, taken `LANES` at a time so the polygon projections can
+/// be batched.
+fn collide_polygon_on_lines<S, I, F>(lines: I, a: &Polygon<S>, project_b: F) -> Option<Vector<S>>
+where
+    S: Scalar,
+    I: Iterator<Item = Rectangle<S>>,
+    F: Fn(&[Rectangle<S>]) -> [Vector<S>; LANES],
+{
+    let mut res = MinAxis::new();
+
+    let mut batch = [Rectangle::new_vec(Vector::zero(), Vector::zero()); LANES];
+    let mut len = 0;
+    let mut lines = lines.peekable();
+    while let Some(line) = lines.next() {
+        batch[len] = line;
+        len += 1;
+        if len < LANES && lines.peek().is_some() {
+            continue;
         }
-        if c.abs() < min {
-            min = c.abs();
-            res = Some(line.size * c);
+
+        let a_proj = project_polygon_lanes(&batch[..len], a);
+        let b_proj = project_b(&batch[..len]);
+        for k in 0..len {
+            if !res.add(
+                batch[k].size,
+                one_dimensional_collision(b_proj[k], a_proj[k]),
+            ) {
+                return None;
+            }
         }
+        len = 0;
     }
-    res
+    res.res
 }
 
-pub fn one_dimensional_collision(a: Vector, b: Vector) -> f64 {
-    println!("{:?} {:?}", a, b);
+pub fn one_dimensional_collision<S: Scalar>(a: Vector<S>, b: Vector<S>) -> Overlap<S> {
     if a.y < b.x || a.x > b.y {
-        return f64::NAN;
+        return Overlap::Separated;
     }
-    if a.y < b.y && a.x < b.x {
-        return b.x - a.y;
+    let backward = b.x - a.y;
+    let forward = b.y - a.x;
+    if forward.abs() < backward.abs() {
+        Overlap::Penetration(forward)
+    } else {
+        Overlap::Penetration(backward)
     }
-    if a.y > b.y && a.x > b.x {
-        return b.y - a.x;
+}
+
+/// A circle only needs to be tested against the polygon's edge normals and
+/// the axis towards the vertex closest to its centre.
+fn closest_point<S: Scalar, I: Iterator<Item = Vector<S>>>(points: I, to: Vector<S>) -> Vector<S> {
+    let mut res = to;
+    let mut min = None;
+    for point in points {
+        let distance = point.distance_sqr(&to);
+        if min.is_none_or(|min| distance < min) {
+            min = Some(distance);
+            res = point;
+        }
     }
-    f64::MAX
+    res
 }
@@ -0,0 +1,177 @@
+use proptest::prelude::*;
+
+use crate::{
+    circle::CircleObject, geometry::Vector, object::MetaObject, polyhedron::PolyhedronObject,
+    rectangle::RectangleObject,
+};
+
+const EPS: f64 = 1e-6;
+
+#[derive(Clone, Copy, Debug)]
+enum Shape {
+    Rect(f64, f64),
+    Circle(f64),
+    Poly(f64, usize),
+}
+
+impl Shape {
+    fn build(self) -> MetaObject {
+        match self {
+            Shape::Rect(w, h) => RectangleObject::new_obj(w, h),
+            Shape::Circle(radius) => CircleObject::new_obj(radius),
+            Shape::Poly(radius, points) => PolyhedronObject::new_obj(radius, points),
+        }
+    }
+}
+
+fn shape() -> impl Strategy<Value = Shape> {
+    prop_oneof![
+        (1.0..50.0, 1.0..50.0).prop_map(|(w, h)| Shape::Rect(w, h)),
+        (1.0..25.0).prop_map(Shape::Circle),
+        (1.0..25.0, 3..=24_usize).prop_map(|(r, n)| Shape::Poly(r, n)),
+    ]
+}
+
+fn position() -> impl Strategy<Value = Vector> {
+    (-40.0..40.0, -40.0..40.0).prop_map(|(x, y)| Vector::new(x, y))
+}
+
+/// World-space outline of a shape, or `None` for circles.
+fn outline(shape: &MetaObject, position: Vector) -> Option<Vec<Vector>> {
+    match shape {
+        MetaObject::Rect(rect) => Some(rect.at(position).points().collect()),
+        MetaObject::Circle(_) => None,
+        MetaObject::Poly(poly) => Some(poly.at(position).points().collect()),
+    }
+}
+
+fn signed_area(polygon: &[Vector]) -> f64 {
+    let mut area = 0.0;
+    for i in 0..polygon.len() {
+        area += polygon[i].cross(&polygon[(i + 1) % polygon.len()]);
+    }
+    area / 2.0
+}
+
+/// Sutherland-Hodgman clipping of `subject` by the convex `clip`.
+fn clip(subject: &[Vector], clip: &[Vector]) -> Vec<Vector> {
+    let orientation = signed_area(clip).signum();
+    let inside = |a: Vector, b: Vector, p: Vector| (b - a).cross(&(p - a)) * orientation >= 0.0;
+
+    let mut output = subject.to_vec();
+    for i in 0..clip.len() {
+        let (a, b) = (clip[i], clip[(i + 1) % clip.len()]);
+        let input = std::mem::take(&mut output);
+        for k in 0..input.len() {
+            let (p, q) = (input[k], input[(k + 1) % input.len()]);
+            let intersection = || {
+                let t = (b - a).cross(&(p - a)) / (b - a).cross(&(p - q));
+                p.lerp(&q, t)
+            };
+            match (inside(a, b, p), inside(a, b, q)) {
+                (true, true) => output.push(q),
+                (true, false) => output.push(intersection()),
+                (false, true) => {
+                    output.push(intersection());
+                    output.push(q);
+                }
+                (false, false) => {}
+            }
+        }
+    }
+    output
+}
+
+fn segment_distance(p: Vector, a: Vector, b: Vector) -> f64 {
+    let ab = b - a;
+    let t = ((p - a).dot(&ab) / ab.dot_sqr()).clamp(0.0, 1.0);
+    p.distance(&a.lerp(&b, t))
+}
+
+/// Distance from `point` to the polygon, zero when inside.
+fn polygon_distance(polygon: &[Vector], point: Vector) -> f64 {
+    if clip(&[point, point, point], polygon).len() == 3 {
+        return 0.0;
+    }
+    (0..polygon.len())
+        .map(|i| segment_distance(point, polygon[i], polygon[(i + 1) % polygon.len()]))
+        .fold(f64::MAX, f64::min)
+}
+
+/// `Some(true)` when the shapes clearly overlap, `Some(false)` when they are
+/// clearly apart and `None` when they only just touch.
+fn reference_overlap(a: &MetaObject, pa: Vector, b: &MetaObject, pb: Vector) -> Option<bool> {
+    let classify = |margin: f64| {
+        if margin > 1e-3 {
+            Some(true)
+        } else if margin < -1e-3 {
+            Some(false)
+        } else {
+            None
+        }
+    };
+    match (outline(a, pa), outline(b, pb)) {
+        (Some(a), Some(b)) => {
+            let area = signed_area(&clip(&a, &b)).abs();
+            if area > 1e-3 {
+                return Some(true);
+            }
+            let gap = a
+                .iter()
+                .map(|&p| polygon_distance(&b, p))
+                .chain(b.iter().map(|&p| polygon_distance(&a, p)))
+                .fold(f64::MAX, f64::min);
+            classify(-gap)
+        }
+        (Some(polygon), None) | (None, Some(polygon)) => {
+            let (circle, center) = match (a, b) {
+                (MetaObject::Circle(circle), _) => (circle, pa),
+                (_, MetaObject::Circle(circle)) => (circle, pb),
+                _ => unreachable!(),
+            };
+            classify(circle.radius - polygon_distance(&polygon, center))
+        }
+        (None, None) => match (a, b) {
+            (MetaObject::Circle(ca), MetaObject::Circle(cb)) => {
+                classify(ca.radius + cb.radius - pa.distance(&pb))
+            }
+            _ => unreachable!(),
+        },
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2048))]
+
+    #[test]
+    fn collisions_are_antisymmetric(a in shape(), pa in position(), b in shape(), pb in position()) {
+        let (a, b) = (a.build(), b.build());
+        let ab = a.collides_with(pa, &b, pb);
+        let ba = b.collides_with(pb, &a, pa);
+        match (ab, ba) {
+            (Some(ab), Some(ba)) => prop_assert!((ab + ba).len() < EPS, "{:?} {:?}", ab, ba),
+            (Some(v), None) | (None, Some(v)) => prop_assert!(v.len() < EPS, "{:?}", v),
+            (None, None) => {}
+        }
+    }
+
+    #[test]
+    fn applying_the_vector_separates(a in shape(), pa in position(), b in shape(), pb in position()) {
+        let (a, b) = (a.build(), b.build());
+        if let Some(vector) = a.collides_with(pa, &b, pb) {
+            let pb = pb + vector * (1.0 + EPS);
+            if let Some(rest) = a.collides_with(pa, &b, pb) {
+                prop_assert!(rest.len() < EPS, "{:?} left {:?}", vector, rest);
+            }
+        }
+    }
+
+    #[test]
+    fn collisions_match_reference(a in shape(), pa in position(), b in shape(), pb in position()) {
+        let (a, b) = (a.build(), b.build());
+        if let Some(expected) = reference_overlap(&a, pa, &b, pb) {
+            let found = a.collides_with(pa, &b, pb).is_some_and(|v| v.len() > EPS);
+            prop_assert_eq!(found, expected);
+        }
+    }
+}
@@ -56,9 +56,9 @@ export function draw(context, objects) {
     }
 }
 
-export function draw_quad_tree(context, tree, x1, y1) {
+export function draw_quad_tree(context, tree) {
     console.log(tree.length)
-    draw_quad_tree_helper(context, tree, 0.0, 0.0, x1, y1, 0)
+    draw_quad_tree_helper(context, tree, tree[0], tree[1], tree[2], tree[3], 4)
 }
 
 function draw_quad_tree_helper(context, tree, x0, y0, x1, y1, i) {
@@ -0,0 +1,30 @@
+use crate::{body::BodyHandle, geometry::Vector};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverlapPhase {
+    Begin,
+    Stay,
+    End,
+}
+
+/// A touching pair as seen by one `World::tick`. `normal` points from `a`
+/// towards `b`; for `End` events it and `depth` hold the last known contact.
+#[derive(Clone, Copy, Debug)]
+pub struct CollisionEvent {
+    pub a: BodyHandle,
+    pub b: BodyHandle,
+    pub normal: Vector,
+    pub depth: f64,
+    pub phase: OverlapPhase,
+    pub sensor: bool,
+}
+
+/// First body touched by `World::cast_shape`. The shape can travel
+/// `fraction` of the motion without overlapping it; `normal` points from the
+/// body's surface back towards the shape.
+#[derive(Clone, Copy, Debug)]
+pub struct ShapeHit {
+    pub body: BodyHandle,
+    pub fraction: f64,
+    pub normal: Vector,
+}
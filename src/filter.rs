@@ -0,0 +1,34 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollisionFilter {
+    pub category: u32,
+    pub mask: u32,
+    pub group: u32,
+}
+
+impl CollisionFilter {
+    pub fn new(category: u32, mask: u32) -> Self {
+        Self {
+            category,
+            mask,
+            group: 0,
+        }
+    }
+
+    /// Bodies sharing a non-zero group never collide, whatever their masks say.
+    pub fn with_group(self, group: u32) -> Self {
+        Self { group, ..self }
+    }
+
+    pub fn should_collide(&self, other: &CollisionFilter) -> bool {
+        if self.group != 0 && self.group == other.group {
+            return false;
+        }
+        self.mask & other.category != 0 && other.mask & self.category != 0
+    }
+}
+
+impl Default for CollisionFilter {
+    fn default() -> Self {
+        Self::new(1, u32::MAX)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::filter::CollisionFilter;
+
+const PLAYER: u32 = 1 << 0;
+const PICKUP: u32 = 1 << 1;
+const DEBRIS: u32 = 1 << 2;
+const PROJECTILE: u32 = 1 << 3;
+
+#[test]
+fn test_default_filters_collide() {
+    assert!(CollisionFilter::default().should_collide(&CollisionFilter::default()));
+}
+
+#[test]
+fn test_mask_is_checked_both_ways() {
+    let player = CollisionFilter::new(PLAYER, u32::MAX);
+    let pickup = CollisionFilter::new(PICKUP, PLAYER);
+    let debris = CollisionFilter::new(DEBRIS, !DEBRIS);
+
+    assert!(pickup.should_collide(&player));
+    assert!(player.should_collide(&pickup));
+    assert!(!pickup.should_collide(&debris));
+    assert!(!debris.should_collide(&pickup));
+    assert!(!debris.should_collide(&debris));
+    assert!(debris.should_collide(&player));
+}
+
+#[test]
+fn test_same_group_never_collides() {
+    let owner = CollisionFilter::new(PLAYER, u32::MAX).with_group(7);
+    let projectile = CollisionFilter::new(PROJECTILE, u32::MAX).with_group(7);
+    let other = CollisionFilter::new(PLAYER, u32::MAX).with_group(8);
+
+    assert!(!projectile.should_collide(&owner));
+    assert!(projectile.should_collide(&other));
+}
@@ -1,117 +1,183 @@
-#[derive(Clone, Copy, Debug)]
-pub struct Vector {
-    pub x: f64,
-    pub y: f64,
+use crate::scalar::Scalar;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vector<S = f64> {
+    pub x: S,
+    pub y: S,
 }
 
-impl Vector {
-    pub fn new(x: f64, y: f64) -> Self {
+impl<S: Scalar> Vector<S> {
+    pub fn new(x: S, y: S) -> Self {
         Self { x, y }
     }
 
     pub fn zero() -> Self {
-        Self::new(0.0, 0.0)
+        Self::new(S::ZERO, S::ZERO)
     }
 
-    pub fn x(&self) -> f64 {
+    pub fn x(&self) -> S {
         self.x
     }
 
-    pub fn y(&self) -> f64 {
+    pub fn y(&self) -> S {
         self.y
     }
 
-    pub fn dot(&self, other: &Self) -> f64 {
+    pub fn dot(&self, other: &Self) -> S {
         self.x() * other.x() + self.y() * other.y()
     }
 
-    pub fn dot_sqr(&self) -> f64 {
-        self.x().powi(2) + self.y().powi(2)
+    pub fn dot_sqr(&self) -> S {
+        self.x() * self.x() + self.y() * self.y()
     }
 
-    pub fn len(&self) -> f64 {
+    pub fn len(&self) -> S {
         self.dot_sqr().sqrt()
     }
 
     pub fn norm(&self) -> Self {
-        *self * (1.0 / self.len())
+        *self * (S::ONE / self.len())
+    }
+
+    /// `None` for zero-length or NaN vectors, which `norm` would turn into
+    /// NaNs.
+    pub fn try_norm(&self) -> Option<Self> {
+        let len = self.len();
+        if len > S::ZERO {
+            Some(*self * (S::ONE / len))
+        } else {
+            None
+        }
+    }
+
+    /// The z component of the 3D cross product.
+    pub fn cross(&self, other: &Self) -> S {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Rotated a quarter turn counter-clockwise.
+    pub fn perp(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    pub fn rotate(&self, rot: Rot<S>) -> Self {
+        rot.apply(*self)
+    }
+
+    pub fn lerp(&self, other: &Self, t: S) -> Self {
+        *self + (*other - *self) * t
+    }
+
+    /// Angle from the x axis, in radians.
+    pub fn angle(&self) -> S {
+        self.y.atan2(self.x)
+    }
+
+    pub fn distance(&self, other: &Self) -> S {
+        (*other - *self).len()
+    }
+
+    pub fn distance_sqr(&self, other: &Self) -> S {
+        (*other - *self).dot_sqr()
+    }
+}
+
+impl<S: Scalar> std::ops::Neg for Vector<S> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<S: Scalar> std::ops::Div<S> for Vector<S> {
+    type Output = Self;
+    fn div(self, right: S) -> Self {
+        Self::new(self.x / right, self.y / right)
     }
 }
 
-impl std::ops::Mul<f64> for Vector {
+/// Component-wise product.
+impl<S: Scalar> std::ops::Mul<Vector<S>> for Vector<S> {
     type Output = Self;
-    fn mul(self, right: f64) -> Self {
+    fn mul(self, right: Vector<S>) -> Self {
+        Self::new(self.x * right.x, self.y * right.y)
+    }
+}
+
+impl<S: Scalar> std::ops::Mul<S> for Vector<S> {
+    type Output = Self;
+    fn mul(self, right: S) -> Self {
         Self::new(self.x * right, self.y * right)
     }
 }
 
-impl std::ops::Add<Vector> for Vector {
+impl<S: Scalar> std::ops::Add<Vector<S>> for Vector<S> {
     type Output = Self;
-    fn add(self, right: Vector) -> Self {
+    fn add(self, right: Vector<S>) -> Self {
         Self::new(self.x + right.x, self.y + right.y)
     }
 }
 
-impl std::ops::AddAssign<Vector> for Vector {
-    fn add_assign(&mut self, right: Vector) {
+impl<S: Scalar> std::ops::AddAssign<Vector<S>> for Vector<S> {
+    fn add_assign(&mut self, right: Vector<S>) {
         self.x += right.x;
         self.y += right.y;
     }
 }
 
-impl std::ops::Sub<Vector> for Vector {
+impl<S: Scalar> std::ops::Sub<Vector<S>> for Vector<S> {
     type Output = Self;
-    fn sub(self, right: Vector) -> Self {
+    fn sub(self, right: Vector<S>) -> Self {
         Self::new(self.x - right.x, self.y - right.y)
     }
 }
 
-impl std::ops::SubAssign<Vector> for Vector {
-    fn sub_assign(&mut self, right: Vector) {
+impl<S: Scalar> std::ops::SubAssign<Vector<S>> for Vector<S> {
+    fn sub_assign(&mut self, right: Vector<S>) {
         self.x -= right.x;
         self.y -= right.y;
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-pub struct Rectangle {
-    pub coord: Vector,
-    pub size: Vector,
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rectangle<S = f64> {
+    pub coord: Vector<S>,
+    pub size: Vector<S>,
 }
 
-impl Rectangle {
-    pub fn new(x: f64, y: f64, w: f64, h: f64) -> Self {
+impl<S: Scalar> Rectangle<S> {
+    pub fn new(x: S, y: S, w: S, h: S) -> Self {
         Self {
             coord: Vector::new(x, y),
             size: Vector::new(w, h),
         }
     }
 
-    pub fn new_vec(coord: Vector, size: Vector) -> Self {
+    pub fn new_vec(coord: Vector<S>, size: Vector<S>) -> Self {
         Self { coord, size }
     }
 
-    pub fn left(&self) -> f64 {
+    pub fn left(&self) -> S {
         self.coord.x
     }
 
-    pub fn right(&self) -> f64 {
+    pub fn right(&self) -> S {
         self.coord.x + self.size.x
     }
 
-    pub fn top(&self) -> f64 {
+    pub fn top(&self) -> S {
         self.coord.y
     }
 
-    pub fn bottom(&self) -> f64 {
+    pub fn bottom(&self) -> S {
         self.coord.y + self.size.y
     }
 
-    pub fn center(&self) -> Vector {
-        self.coord + self.coord * 0.5
+    pub fn center(&self) -> Vector<S> {
+        self.coord + self.size * S::from_f64(0.5)
     }
 
-    pub fn points(&self) -> impl Iterator<Item = Vector> {
+    pub fn points(&self) -> impl Iterator<Item = Vector<S>> {
         [
             Vector::new(self.left(), self.top()),
             Vector::new(self.right(), self.top()),
@@ -121,21 +187,195 @@ impl Rectangle {
         .into_iter()
     }
 
-    pub fn collides_with(&self, other: &Rectangle) -> bool {
+    pub fn contains(&self, other: &Rectangle<S>) -> bool {
+        self.left() <= other.left()
+            && self.right() >= other.right()
+            && self.top() <= other.top()
+            && self.bottom() >= other.bottom()
+    }
+
+    pub fn contains_point(&self, point: Vector<S>) -> bool {
+        self.left() <= point.x
+            && self.right() >= point.x
+            && self.top() <= point.y
+            && self.bottom() >= point.y
+    }
+
+    pub fn collides_with(&self, other: &Rectangle<S>) -> bool {
         !(self.left() > other.right()
             || self.right() < other.left()
             || self.top() > other.bottom()
             || self.bottom() < other.top())
     }
+
+    /// The smallest rectangle containing both.
+    pub fn union(&self, other: &Rectangle<S>) -> Self {
+        let left = min(self.left(), other.left());
+        let top = min(self.top(), other.top());
+        let right = max(self.right(), other.right());
+        let bottom = max(self.bottom(), other.bottom());
+        Self::new(left, top, right - left, bottom - top)
+    }
+
+    /// The overlapping part, or `None` if the rectangles do not touch.
+    pub fn intersection(&self, other: &Rectangle<S>) -> Option<Self> {
+        if !self.collides_with(other) {
+            return None;
+        }
+        let left = max(self.left(), other.left());
+        let top = max(self.top(), other.top());
+        let right = min(self.right(), other.right());
+        let bottom = min(self.bottom(), other.bottom());
+        Some(Self::new(left, top, right - left, bottom - top))
+    }
+
+    /// Grows every side outwards by `margin`.
+    pub fn expand(&self, margin: S) -> Self {
+        let margin = Vector::new(margin, margin);
+        Self::new_vec(self.coord - margin, self.size + margin + margin)
+    }
+
+    pub fn area(&self) -> S {
+        self.size.x * self.size.y
+    }
+}
+
+fn min<S: Scalar>(a: S, b: S) -> S {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn max<S: Scalar>(a: S, b: S) -> S {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+/// A rotation stored as its cosine and sine.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rot<S = f64> {
+    pub cos: S,
+    pub sin: S,
+}
+
+impl<S: Scalar> Rot<S> {
+    pub fn identity() -> Self {
+        Self {
+            cos: S::ONE,
+            sin: S::ZERO,
+        }
+    }
+
+    pub fn from_angle(angle: S) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self { cos, sin }
+    }
+
+    pub fn angle(&self) -> S {
+        self.sin.atan2(self.cos)
+    }
+
+    pub fn inverse(&self) -> Self {
+        Self {
+            cos: self.cos,
+            sin: -self.sin,
+        }
+    }
+
+    pub fn apply(&self, vector: Vector<S>) -> Vector<S> {
+        Vector::new(
+            self.cos * vector.x - self.sin * vector.y,
+            self.sin * vector.x + self.cos * vector.y,
+        )
+    }
+}
+
+/// `self * right` rotates by `right` first, then by `self`.
+impl<S: Scalar> std::ops::Mul<Rot<S>> for Rot<S> {
+    type Output = Self;
+    fn mul(self, right: Rot<S>) -> Self {
+        Self {
+            cos: self.cos * right.cos - self.sin * right.sin,
+            sin: self.sin * right.cos + self.cos * right.sin,
+        }
+    }
 }
 
-pub fn project_point(line: &Rectangle, point: Vector) -> f64 {
+/// Taylor series in plain arithmetic: `f64::sin` comes from the platform's
+/// libm and may differ in the last bit between machines.
+pub(crate) fn sin_cos(angle: f64) -> (f64, f64) {
+    let pi = std::f64::consts::PI;
+    let x = angle - (angle / (2.0 * pi)).round() * 2.0 * pi;
+    let x2 = x * x;
+
+    let mut sin = 0.0;
+    let mut cos = 0.0;
+    let mut sin_term = x;
+    let mut cos_term = 1.0;
+    for k in 1..=15 {
+        sin += sin_term;
+        cos += cos_term;
+        let k = k as f64;
+        sin_term *= -x2 / ((2.0 * k) * (2.0 * k + 1.0));
+        cos_term *= -x2 / ((2.0 * k - 1.0) * (2.0 * k));
+    }
+    (sin, cos)
+}
+
+/// A rotation followed by a translation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform<S = f64> {
+    pub translation: Vector<S>,
+    pub rot: Rot<S>,
+}
+
+impl<S: Scalar> Transform<S> {
+    pub fn new(translation: Vector<S>, rot: Rot<S>) -> Self {
+        Self { translation, rot }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(Vector::zero(), Rot::identity())
+    }
+
+    pub fn apply(&self, point: Vector<S>) -> Vector<S> {
+        self.rot.apply(point) + self.translation
+    }
+
+    /// Directions are rotated but not moved.
+    pub fn apply_vector(&self, vector: Vector<S>) -> Vector<S> {
+        self.rot.apply(vector)
+    }
+
+    pub fn inverse(&self) -> Self {
+        let rot = self.rot.inverse();
+        Self::new(-rot.apply(self.translation), rot)
+    }
+}
+
+/// `self * right` applies `right` first, then `self`.
+impl<S: Scalar> std::ops::Mul<Transform<S>> for Transform<S> {
+    type Output = Self;
+    fn mul(self, right: Transform<S>) -> Self {
+        Self::new(self.apply(right.translation), self.rot * right.rot)
+    }
+}
+
+pub fn project_point<S: Scalar>(line: &Rectangle<S>, point: Vector<S>) -> S {
     (point - line.coord).dot(&line.size)
 }
 
-pub fn project_points<I: Iterator<Item = Vector>>(line: &Rectangle, points: I) -> Vector {
-    let mut min = f64::MAX;
-    let mut max = f64::MIN;
+pub fn project_points<S: Scalar, I: Iterator<Item = Vector<S>>>(
+    line: &Rectangle<S>,
+    points: I,
+) -> Vector<S> {
+    let mut min = S::MAX;
+    let mut max = S::MIN;
     for point in points {
         let projection = project_point(line, point);
         if projection < min {
@@ -148,7 +388,189 @@ pub fn project_points<I: Iterator<Item = Vector>>(line: &Rectangle, points: I) -
     Vector::new(min, max)
 }
 
-pub fn project_circle(line: &Rectangle, center: Vector, radius: f64) -> Vector {
+pub fn project_polygon<S: Scalar>(line: &Rectangle<S>, polygon: &Polygon<S>) -> Vector<S> {
+    let offset = project_point(line, polygon.center);
+    let mut min = S::MAX;
+    let mut max = S::MIN;
+    for point in polygon.points {
+        let projection = point.dot(&line.size);
+        if projection < min {
+            min = projection;
+        }
+        if projection > max {
+            max = projection;
+        }
+    }
+    Vector::new(offset + min, offset + max)
+}
+
+pub const LANES: usize = 4;
+
+/// Projects `polygon` on up to `LANES` axes at once. Lanes past
+/// `lines.len()` are left zeroed.
+pub fn project_polygon_lanes<S: Scalar>(
+    lines: &[Rectangle<S>],
+    polygon: &Polygon<S>,
+) -> [Vector<S>; LANES] {
+    S::project_polygon_lanes(lines, polygon)
+}
+
+pub fn project_polygon_lanes_scalar<S: Scalar>(
+    lines: &[Rectangle<S>],
+    polygon: &Polygon<S>,
+) -> [Vector<S>; LANES] {
+    let mut res = [Vector::zero(); LANES];
+    for (k, line) in lines.iter().enumerate() {
+        res[k] = project_polygon(line, polygon);
+    }
+    res
+}
+
+/// Every lane holds one axis and each point is broadcast across them, so no
+/// horizontal reduction is needed. Gives the same bits as the scalar path.
+#[cfg(feature = "simd")]
+pub fn project_polygon_lanes_simd(lines: &[Rectangle], polygon: &Polygon) -> [Vector; LANES] {
+    use wide::f64x4;
+
+    let mut axis_x = [0.0; LANES];
+    let mut axis_y = [0.0; LANES];
+    for (k, line) in lines.iter().enumerate() {
+        axis_x[k] = line.size.x;
+        axis_y[k] = line.size.y;
+    }
+    let axis_x = f64x4::from(axis_x);
+    let axis_y = f64x4::from(axis_y);
+
+    let mut min = f64x4::splat(f64::MAX);
+    let mut max = f64x4::splat(f64::MIN);
+    for point in polygon.points {
+        let projection = axis_x * point.x + axis_y * point.y;
+        min = min.fast_min(projection);
+        max = max.fast_max(projection);
+    }
+
+    let min = min.to_array();
+    let max = max.to_array();
+    let mut res = [Vector::zero(); LANES];
+    for (k, line) in lines.iter().enumerate() {
+        let offset = project_point(line, polygon.center);
+        res[k] = Vector::new(offset + min[k], offset + max[k]);
+    }
+    res
+}
+
+pub fn project_circle<S: Scalar>(line: &Rectangle<S>, center: Vector<S>, radius: S) -> Vector<S> {
     let c = project_point(line, center);
     Vector::new(c - radius, c + radius)
-}
\ No newline at end of file
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Circle<S = f64> {
+    pub center: Vector<S>,
+    pub radius: S,
+}
+
+impl<S: Scalar> Circle<S> {
+    pub fn new(center: Vector<S>, radius: S) -> Self {
+        Self { center, radius }
+    }
+
+    pub fn aabb(&self) -> Rectangle<S> {
+        let rad = Vector::new(self.radius, self.radius);
+        Rectangle::new_vec(self.center - rad, rad + rad)
+    }
+}
+
+/// A convex polygon given by points relative to `center`.
+#[derive(Clone, Copy, Debug)]
+pub struct Polygon<'a, S = f64> {
+    pub center: Vector<S>,
+    pub points: &'a [Vector<S>],
+}
+
+impl<'a, S: Scalar> Polygon<'a, S> {
+    pub fn new(center: Vector<S>, points: &'a [Vector<S>]) -> Self {
+        Self { center, points }
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Vector<S>> + 'a {
+        let center = self.center;
+        self.points.iter().map(move |it| *it + center)
+    }
+
+    /// Edge normals. Edges between coincident points have no normal and are
+    /// skipped.
+    pub fn lines(&self) -> impl Iterator<Item = Rectangle<S>> + 'a {
+        let center = self.center;
+        let points = self.points;
+        (0..points.len()).filter_map(move |i| {
+            let j = (i + 1) % points.len();
+
+            let p_vec = (points[i] - points[j]).try_norm()?.perp();
+
+            Some(Rectangle::new_vec(points[i] + center, p_vec))
+        })
+    }
+}
+
+#[cfg(feature = "glam")]
+mod glam_interop {
+    use super::Vector;
+
+    impl From<glam::DVec2> for Vector<f64> {
+        fn from(v: glam::DVec2) -> Self {
+            Self::new(v.x, v.y)
+        }
+    }
+
+    impl From<Vector<f64>> for glam::DVec2 {
+        fn from(v: Vector<f64>) -> Self {
+            Self::new(v.x, v.y)
+        }
+    }
+
+    impl From<glam::Vec2> for Vector<f32> {
+        fn from(v: glam::Vec2) -> Self {
+            Self::new(v.x, v.y)
+        }
+    }
+
+    impl From<Vector<f32>> for glam::Vec2 {
+        fn from(v: Vector<f32>) -> Self {
+            Self::new(v.x, v.y)
+        }
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_interop {
+    use super::{Rot, Vector};
+    use crate::scalar::Scalar;
+
+    impl<S: Scalar + nalgebra::Scalar> From<nalgebra::Vector2<S>> for Vector<S> {
+        fn from(v: nalgebra::Vector2<S>) -> Self {
+            Self::new(v.x, v.y)
+        }
+    }
+
+    impl<S: Scalar + nalgebra::Scalar> From<Vector<S>> for nalgebra::Vector2<S> {
+        fn from(v: Vector<S>) -> Self {
+            Self::new(v.x, v.y)
+        }
+    }
+
+    impl From<nalgebra::UnitComplex<f64>> for Rot<f64> {
+        fn from(rot: nalgebra::UnitComplex<f64>) -> Self {
+            Self {
+                cos: rot.cos_angle(),
+                sin: rot.sin_angle(),
+            }
+        }
+    }
+
+    impl From<Rot<f64>> for nalgebra::UnitComplex<f64> {
+        fn from(rot: Rot<f64>) -> Self {
+            Self::from_cos_sin_unchecked(rot.cos, rot.sin)
+        }
+    }
+}
@@ -1,46 +1,143 @@
 use crate::{
-    circle::CircleObject,
-    collisions::collide_circle_rect,
-    geometry::{project_point, Rectangle, Vector},
-    rectangle::RectangleObject,
+    collisions::{collide_circle_rect, collide_cirle_circle, collide_poly_circle},
+    geometry::{project_point, Circle, Polygon, Rectangle, Rot, Transform, Vector},
 };
 
 #[test]
 fn test_point_projection_on_vertical() {
-    let line = Rectangle::new(0.0, 0.0, 0.0, 1.0);
+    let line: Rectangle = Rectangle::new(0.0, 0.0, 0.0, 1.0);
     let point = Vector::new(2.0, 2.0);
     assert!((project_point(&line, point) - 2.0).abs() < 0.000001);
 }
 
 #[test]
 fn test_point_projection_on_horisontal() {
-    let line = Rectangle::new(0.0, 0.0, 1.0, 0.0);
+    let line: Rectangle = Rectangle::new(0.0, 0.0, 1.0, 0.0);
     let point = Vector::new(2.0, 2.0);
     assert!((project_point(&line, point) - 2.0).abs() < 0.000001);
 }
 
 #[test]
 fn test_point_projection_on_diagonal() {
-    let line = Rectangle::new_vec(Vector::new(0.0, 0.0), Vector::new(1.0, 1.0).norm());
+    let line: Rectangle = Rectangle::new_vec(Vector::new(0.0, 0.0), Vector::new(1.0, 1.0).norm());
     let point = Vector::new(1.0, 1.0);
     assert!((project_point(&line, point) - Vector::new(1.0, 1.0).len()).abs() < 0.000001);
 }
 
 #[test]
 fn test_point_projection_on_non_zero() {
-    let line = Rectangle::new_vec(Vector::new(1.0, 1.0), Vector::new(1.0, 1.0).norm());
+    let line: Rectangle = Rectangle::new_vec(Vector::new(1.0, 1.0), Vector::new(1.0, 1.0).norm());
     let point = Vector::new(2.0, 2.0);
     assert!((project_point(&line, point) - Vector::new(1.0, 1.0).len()).abs() < 0.000001);
 }
 
 #[test]
 fn test_circle_rect_collision() {
-    let circle = CircleObject::new(Vector::new(50.0, 50.0), 30.0, Vector::zero());
-    let rect = RectangleObject::new(10.0, 10.0, 100.0, 30.0, Vector::zero());
+    let circle = Circle::new(Vector::new(50.0, 50.0), 30.0);
+    let rect = Rectangle::new(10.0, 10.0, 100.0, 30.0);
 
     if let Some(collision) = collide_circle_rect(&circle, &rect) {
-        assert!((collision - Vector::new(0.0, 20.0)).len() < 0.0000001);
+        assert!((collision - Vector::new(0.0, -20.0)).len() < 0.0000001);
     } else {
         panic!("Expected to find collision");
     }
 }
+
+#[test]
+fn test_vector_ops() {
+    let a = Vector::new(3.0, 4.0);
+    let b = Vector::new(1.0, -2.0);
+
+    assert_eq!(-a, Vector::new(-3.0, -4.0));
+    assert_eq!(a / 2.0, Vector::new(1.5, 2.0));
+    assert_eq!(a * b, Vector::new(3.0, -8.0));
+    assert_eq!(a.cross(&b), -10.0);
+    assert_eq!(a.perp(), Vector::new(-4.0, 3.0));
+    assert_eq!(a.perp().dot(&a), 0.0);
+    assert_eq!(a.lerp(&b, 0.5), Vector::new(2.0, 1.0));
+    assert_eq!(a.distance(&Vector::zero()), 5.0);
+    assert_eq!(a.distance_sqr(&b), 40.0);
+    assert!((Vector::new(0.0, 2.0).angle() - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+}
+
+#[test]
+fn test_rectangle_ops() {
+    let a = Rectangle::new(0.0, 0.0, 10.0, 20.0);
+    let b = Rectangle::new(5.0, 10.0, 10.0, 20.0);
+
+    assert_eq!(a.center(), Vector::new(5.0, 10.0));
+    assert_eq!(a.area(), 200.0);
+    assert_eq!(a.union(&b), Rectangle::new(0.0, 0.0, 15.0, 30.0));
+    assert_eq!(
+        a.intersection(&b),
+        Some(Rectangle::new(5.0, 10.0, 5.0, 10.0))
+    );
+    assert_eq!(a.intersection(&Rectangle::new(11.0, 0.0, 1.0, 1.0)), None);
+    assert_eq!(a.expand(1.0), Rectangle::new(-1.0, -1.0, 12.0, 22.0));
+    assert!(a.contains_point(Vector::new(10.0, 0.0)));
+    assert!(!a.contains_point(Vector::new(10.5, 0.0)));
+}
+
+#[test]
+fn test_rot_and_transform() {
+    let quarter = Rot::from_angle(std::f64::consts::FRAC_PI_2);
+    let v = Vector::new(2.0, 1.0);
+    assert!((v.rotate(quarter) - v.perp()).len() < 1e-12);
+    assert!(((quarter * quarter).angle() - std::f64::consts::PI).abs() < 1e-12);
+    assert!((v.rotate(quarter).rotate(quarter.inverse()) - v).len() < 1e-12);
+
+    let transform = Transform::new(Vector::new(10.0, -5.0), quarter);
+    let point = transform.apply(v);
+    assert!((point - Vector::new(9.0, -3.0)).len() < 1e-12);
+    assert!((transform.inverse().apply(point) - v).len() < 1e-12);
+    assert!(((transform * transform.inverse()).apply(v) - v).len() < 1e-12);
+    assert!((transform.apply_vector(v) - v.perp()).len() < 1e-12);
+    assert_eq!(Transform::identity().apply(v), v);
+}
+
+#[test]
+fn test_coincident_centres_give_finite_vectors() {
+    let finite = |v: Vector| v.x.is_finite() && v.y.is_finite() && v.len() > 0.0;
+
+    let circle = Circle::new(Vector::new(5.0, 5.0), 2.0);
+    assert!(finite(collide_cirle_circle(&circle, &circle).unwrap()));
+
+    let rect = Rectangle::new(0.0, 0.0, 10.0, 10.0);
+    assert!(finite(collide_circle_rect(&circle, &rect).unwrap()));
+
+    let points = [
+        Vector::new(-1.0, -1.0),
+        Vector::new(1.0, -1.0),
+        Vector::new(1.0, -1.0),
+        Vector::new(0.0, 1.0),
+    ];
+    let poly = Polygon::new(circle.center, &points);
+    assert_eq!(poly.lines().count(), 3);
+    assert!(finite(collide_poly_circle(&poly, &circle).unwrap()));
+
+    assert_eq!(Vector::<f64>::zero().try_norm(), None);
+    assert_eq!(Vector::new(f64::NAN, 0.0).try_norm(), None);
+}
+
+#[cfg(feature = "simd")]
+#[test]
+fn test_simd_projection_matches_scalar() {
+    use crate::geometry::{project_polygon_lanes_scalar, project_polygon_lanes_simd};
+    use crate::polyhedron::PolyhedronObject;
+
+    let lines = [
+        Rectangle::new_vec(Vector::new(3.0, 4.0), Vector::new(0.6, -0.8)),
+        Rectangle::new_vec(Vector::new(-1.0, 2.0), Vector::new(1.0, 0.0)),
+        Rectangle::new_vec(Vector::new(0.5, 0.0), Vector::new(0.0, 1.0)),
+    ];
+    for point_count in 3..=10 {
+        let poly = PolyhedronObject::new(30.0, point_count);
+        let polygon = Polygon::new(Vector::new(12.5, -7.0), poly.points());
+        let scalar = project_polygon_lanes_scalar(&lines, &polygon);
+        let simd = project_polygon_lanes_simd(&lines, &polygon);
+        for (a, b) in scalar.iter().zip(simd.iter()) {
+            assert_eq!(a.x.to_bits(), b.x.to_bits());
+            assert_eq!(a.y.to_bits(), b.y.to_bits());
+        }
+    }
+}
@@ -0,0 +1,41 @@
+/// When resting bodies are put to sleep. A sleeping body is not integrated
+/// and not tested against other resting bodies until something wakes it.
+#[derive(Clone, Copy, Debug)]
+pub struct SleepConfig {
+    pub enabled: bool,
+    /// Bodies slower than this count as resting.
+    pub linear_speed: f64,
+    pub angular_speed: f64,
+    /// Seconds every body of an island has to rest before the island sleeps.
+    pub time: f64,
+}
+
+impl Default for SleepConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            linear_speed: 2.0,
+            angular_speed: 0.05,
+            time: 0.5,
+        }
+    }
+}
+
+/// Groups `0..len` into islands joined by `links`, returning the lowest
+/// index of each one's island.
+pub(crate) fn islands(len: usize, links: &[(usize, usize)]) -> Vec<usize> {
+    let mut parents: Vec<_> = (0..len).collect();
+    for &(a, b) in links {
+        let (a, b) = (root(&mut parents, a), root(&mut parents, b));
+        parents[a.max(b)] = a.min(b);
+    }
+    (0..len).map(|index| root(&mut parents, index)).collect()
+}
+
+fn root(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
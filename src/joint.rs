@@ -0,0 +1,55 @@
+use crate::{body::BodyHandle, geometry::Vector};
+
+/// Names a joint in a `World`. Handles are never reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JointHandle(pub u64);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JointKind {
+    /// Keeps the anchors `length` apart; the bodies turn freely.
+    Distance { length: f64 },
+    /// Pins the anchors together; the bodies turn freely.
+    Revolute,
+    /// Lets `b`'s anchor slide only along `axis`, given in `a`'s space, and
+    /// holds `b`'s angle at `angle` from `a`'s.
+    Prismatic { axis: Vector, angle: f64 },
+    /// Pins the anchors together and holds `b`'s angle at `angle` from `a`'s.
+    Weld { angle: f64 },
+    /// Pulls the anchors towards `length` apart with a force of `stiffness`
+    /// per unit of stretch, and `damping` per unit of stretching speed.
+    Spring {
+        length: f64,
+        stiffness: f64,
+        damping: f64,
+    },
+}
+
+/// Connects `a` and `b` at anchors given in each body's own space, so they
+/// turn with the body. Joined bodies still collide; give them a shared filter
+/// group to stop that.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Joint {
+    pub a: BodyHandle,
+    pub b: BodyHandle,
+    pub anchor_a: Vector,
+    pub anchor_b: Vector,
+    pub kind: JointKind,
+}
+
+impl Joint {
+    pub fn new(
+        a: BodyHandle,
+        anchor_a: Vector,
+        b: BodyHandle,
+        anchor_b: Vector,
+        kind: JointKind,
+    ) -> Self {
+        Self {
+            a,
+            b,
+            anchor_a,
+            anchor_b,
+            kind,
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{
+    body::BodyHandle,
+    circle::CircleObject,
+    geometry::{Rot, Vector},
+    joint::{Joint, JointKind},
+    world::World,
+    world_test::add_static,
+};
+
+fn anchor(world: &World, body: BodyHandle, local: Vector) -> Vector {
+    world.position(body).unwrap() + local.rotate(Rot::from_angle(world.angle(body).unwrap()))
+}
+
+fn pin(world: &mut World, position: Vector) -> BodyHandle {
+    add_static(world, CircleObject::new_obj(2.0), position)
+}
+
+#[test]
+fn test_revolute_joint_swings_a_pendulum() {
+    let mut world = World::empty(1000.0, 1000.0);
+    world.set_gravity(Vector::new(0.0, 200.0));
+    let pin = pin(&mut world, Vector::new(500.0, 100.0));
+    let bob = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(600.0, 100.0),
+        Vector::zero(),
+    );
+    let arm = Vector::new(-100.0, 0.0);
+    world.add_joint(Joint::new(
+        pin,
+        Vector::zero(),
+        bob,
+        arm,
+        JointKind::Revolute,
+    ));
+
+    for _ in 0..60 {
+        world.tick(1.0 / 60.0);
+        let error = anchor(&world, bob, arm) - Vector::new(500.0, 100.0);
+        assert!(error.len() < 1.0, "{:?}", error);
+    }
+    assert!(world.position(bob).unwrap().y > 150.0);
+    assert!(world.angle(bob).unwrap() > 0.5);
+}
+
+#[test]
+fn test_distance_and_prismatic_joints_hold() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let a = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(300.0, 300.0),
+        Vector::zero(),
+    );
+    let b = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(350.0, 300.0),
+        Vector::new(0.0, 60.0),
+    );
+    world.add_joint(Joint::new(
+        a,
+        Vector::zero(),
+        b,
+        Vector::zero(),
+        JointKind::Distance { length: 50.0 },
+    ));
+    for _ in 0..60 {
+        world.tick(1.0 / 60.0);
+    }
+    let length = world
+        .position(b)
+        .unwrap()
+        .distance(&world.position(a).unwrap());
+    assert!((length - 50.0).abs() < 1.0, "{}", length);
+
+    let mut world = World::empty(1000.0, 1000.0);
+    world.set_gravity(Vector::new(0.0, 100.0));
+    let rail = pin(&mut world, Vector::new(100.0, 700.0));
+    let slider = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(150.0, 700.0),
+        Vector::new(40.0, 0.0),
+    );
+    world.add_joint(Joint::new(
+        rail,
+        Vector::zero(),
+        slider,
+        Vector::zero(),
+        JointKind::Prismatic {
+            axis: Vector::new(1.0, 0.0),
+            angle: 0.0,
+        },
+    ));
+
+    for _ in 0..60 {
+        world.tick(1.0 / 60.0);
+    }
+    let slider_at = world.position(slider).unwrap();
+    assert!((slider_at.y - 700.0).abs() < 1.0, "{:?}", slider_at);
+    assert!(slider_at.x > 180.0);
+}
+
+#[test]
+fn test_weld_moves_bodies_together() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let a = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(300.0, 300.0),
+        Vector::zero(),
+    );
+    let b = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(330.0, 300.0),
+        Vector::zero(),
+    );
+    let weld = world
+        .add_joint(Joint::new(
+            a,
+            Vector::new(15.0, 0.0),
+            b,
+            Vector::new(-15.0, 0.0),
+            JointKind::Weld { angle: 0.0 },
+        ))
+        .unwrap();
+
+    for _ in 0..30 {
+        world.apply_force(a, Vector::new(0.0, 200.0));
+        world.tick(1.0 / 60.0);
+    }
+    let angle = world.angle(a).unwrap();
+    let offset = world.position(b).unwrap() - world.position(a).unwrap();
+    let expected = Vector::new(30.0, 0.0).rotate(Rot::from_angle(angle));
+    assert!((offset - expected).len() < 1.0, "{:?}", offset);
+    assert!((world.angle(b).unwrap() - angle).abs() < 0.05);
+    let momentum = world.speed(a).unwrap() + world.speed(b).unwrap();
+    assert!(
+        (momentum - Vector::new(0.0, 100.0)).len() < 1e-6,
+        "{:?}",
+        momentum
+    );
+
+    assert!(world.remove_body(b));
+    assert!(world.joint(weld).is_none());
+}
+
+#[test]
+fn test_spring_settles_at_its_length() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let a = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(300.0, 300.0),
+        Vector::zero(),
+    );
+    let b = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(400.0, 300.0),
+        Vector::zero(),
+    );
+    world.add_joint(Joint::new(
+        a,
+        Vector::zero(),
+        b,
+        Vector::zero(),
+        JointKind::Spring {
+            length: 50.0,
+            stiffness: 20.0,
+            damping: 2.0,
+        },
+    ));
+
+    for _ in 0..600 {
+        world.tick(1.0 / 60.0);
+    }
+    let length = world
+        .position(b)
+        .unwrap()
+        .distance(&world.position(a).unwrap());
+    assert!((length - 50.0).abs() < 1.0, "{}", length);
+}
@@ -0,0 +1,36 @@
+pub mod body;
+pub mod boundary;
+pub mod character;
+pub mod circle;
+pub mod collisions;
+pub mod events;
+pub mod filter;
+pub mod geometry;
+pub mod island;
+pub mod joint;
+pub mod object;
+pub mod polyhedron;
+pub mod quad_tree;
+pub mod rectangle;
+pub mod response;
+pub mod scalar;
+pub mod solver;
+pub mod stats;
+pub mod world;
+
+#[cfg(test)]
+mod character_test;
+#[cfg(test)]
+mod collisions_test;
+#[cfg(test)]
+mod filter_test;
+#[cfg(test)]
+mod geometry_test;
+#[cfg(test)]
+mod joint_test;
+#[cfg(test)]
+mod quad_tree_test;
+#[cfg(test)]
+mod scalar_test;
+#[cfg(test)]
+mod world_test;
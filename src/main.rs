@@ -3,19 +3,7 @@ use wasm_bindgen::{prelude::wasm_bindgen, JsCast};
 use web_sys::{window, CanvasRenderingContext2d, HtmlCanvasElement};
 use yew::prelude::*;
 
-mod circle;
-mod collisions;
-mod geometry;
-mod object;
-mod polyhedron;
-mod quad_tree;
-mod rectangle;
-mod world;
-
-#[cfg(test)]
-mod geometry_test;
-
-use world::World;
+use collision_detection::{response::HealthPolicy, stats::StepStats, world::World};
 
 enum Msg {
     Render(f64, bool),
@@ -26,6 +14,8 @@ static DEBUG: bool = true;
 
 struct App {
     world: World,
+    health: HealthPolicy,
+    stats: StepStats,
     last_tick: f64,
     sum_time: f64,
     ticks: u64,
@@ -47,8 +37,14 @@ impl Component for App {
         let height = window.inner_height().unwrap().as_f64().unwrap() as u64 - 30;
         // let width = 1200;
         // let height = 800;
+        #[allow(unused_mut)]
+        let mut world = World::new(width as f64, height as f64);
+        #[cfg(feature = "timings")]
+        world.set_clock(|| web_sys::window().unwrap().performance().unwrap().now() / 1000.0);
         Self {
-            world: World::new(width as f64, height as f64),
+            world,
+            health: HealthPolicy::new(3),
+            stats: StepStats::default(),
             node_ref: NodeRef::default(),
             _render_loop: None,
             last_tick: 0.0,
@@ -83,17 +79,16 @@ impl Component for App {
                 self.ticks += 1;
                 log::info!("{} fps", 1.0 / (self.sum_time / self.ticks as f64));
 
-                self.world.tick(delta_time);
+                self.stats = self.world.tick_with(delta_time, &mut self.health);
                 self.last_tick = time;
 
-                draw(&context, self.world.export());
+                draw(
+                    &context,
+                    self.world.export(|id| self.health.health(id) as f64),
+                );
                 if DEBUG {
-                    draw_quad_tree(
-                        &context,
-                        self.world.export_quad_tree(),
-                        self.width as f64,
-                        self.height as f64,
-                    );
+                    draw_quad_tree(&context, self.world.export_quad_tree());
+                    draw_stats(&context, &self.stats);
                 }
                 let handle = {
                     let link = ctx.link().clone();
@@ -147,6 +142,32 @@ impl Component for App {
     }
 }
 
+fn draw_stats(context: &CanvasRenderingContext2d, stats: &StepStats) {
+    let lines = [
+        format!(
+            "candidates {}  aabb rejected {}  tested {}  contacts {}",
+            stats.candidates, stats.aabb_rejections, stats.narrow_tests, stats.contacts
+        ),
+        format!(
+            "tree depth {}  nodes {}",
+            stats.tree_depth, stats.tree_nodes
+        ),
+        format!(
+            "broad {:.2}ms  narrow {:.2}ms  response {:.2}ms  integrate {:.2}ms",
+            stats.timings.broad * 1000.0,
+            stats.timings.narrow * 1000.0,
+            stats.timings.response * 1000.0,
+            stats.timings.integrate * 1000.0
+        ),
+    ];
+    context.set_font("14px monospace");
+    for (k, line) in lines.iter().enumerate() {
+        context
+            .fill_text(line, 10.0, 20.0 + 18.0 * k as f64)
+            .unwrap();
+    }
+}
+
 fn main() {
     wasm_logger::init(wasm_logger::Config::default());
     yew::start_app::<App>();
@@ -158,5 +179,5 @@ extern "C" {
     pub fn draw(context: &CanvasRenderingContext2d, objects: Box<[f64]>);
 
     #[wasm_bindgen(js_name = "draw_quad_tree")]
-    pub fn draw_quad_tree(context: &CanvasRenderingContext2d, tree: Box<[f64]>, x1: f64, y1: f64);
+    pub fn draw_quad_tree(context: &CanvasRenderingContext2d, tree: Box<[f64]>);
 }
@@ -1,61 +1,94 @@
 use crate::{
     circle::CircleObject,
-    geometry::{Rectangle, Vector},
-    rectangle::RectangleObject, polyhedron::PolyhedronObject,
+    collisions::{
+        collide_circle_rect, collide_cirle_circle, collide_poly_circle, collide_poly_poly,
+        collide_poly_rect, collide_rect_rect,
+    },
+    geometry::{Circle, Polygon, Rectangle, Rot, Vector},
+    polyhedron::PolyhedronObject,
+    rectangle::RectangleObject,
+    scalar::Scalar,
 };
 
-pub enum MetaObject {
-    Rect(RectangleObject),
-    Circle(CircleObject),
-    Poly(PolyhedronObject),
+/// The shape of a body in its own space, centred on the body's position.
+#[derive(Clone)]
+pub enum MetaObject<S = f64> {
+    Rect(RectangleObject<S>),
+    Circle(CircleObject<S>),
+    Poly(PolyhedronObject<S>),
 }
 
-impl MetaObject {
-    pub fn tick(&mut self, delta_time: f64) {
-        match self {
-            MetaObject::Rect(rect) => rect.tick(delta_time),
-            MetaObject::Circle(circle) => circle.tick(delta_time),
-            MetaObject::Poly(poly) => poly.tick(delta_time),
-        }
+impl<S: Scalar> MetaObject<S> {
+    pub fn collides_with(
+        &self,
+        position: Vector<S>,
+        other: &MetaObject<S>,
+        other_position: Vector<S>,
+    ) -> Option<Vector<S>> {
+        self.at(position).collides_with(&other.at(other_position))
     }
 
-    pub fn collides_with(&self, other: &MetaObject) -> Option<Vector> {
+    pub fn at(&self, position: Vector<S>) -> Placed<'_, S> {
         match self {
-            MetaObject::Rect(rect) => rect.collides_with(other),
-            MetaObject::Circle(circle) => circle.collides_with(other),
-            MetaObject::Poly(poly) => poly.collides_with(other),
+            MetaObject::Rect(rect) => Placed::Rect(rect.at(position)),
+            MetaObject::Circle(circle) => Placed::Circle(circle.at(position)),
+            MetaObject::Poly(poly) => Placed::Poly(poly.at(position)),
         }
     }
 
-    pub fn speed(&self) -> Vector {
+    pub fn aabb(&self) -> Rectangle<S> {
         match self {
-            MetaObject::Rect(rect) => rect.speed(),
-            MetaObject::Circle(circle) => circle.speed(),
-            MetaObject::Poly(poly) => poly.speed(),
+            MetaObject::Rect(rect) => rect.aabb(),
+            MetaObject::Circle(circle) => circle.aabb(),
+            MetaObject::Poly(poly) => poly.aabb(),
         }
     }
 
-    pub fn mov(&mut self, direction: Vector) {
+    /// The shape turned by `rot`. A turned rectangle becomes a polygon;
+    /// circles look the same either way.
+    pub fn rotated(&self, rot: Rot<S>) -> Self {
         match self {
-            MetaObject::Rect(rect) => rect.mov(direction),
-            MetaObject::Circle(circle) => circle.mov(direction),
-            MetaObject::Poly(poly) => poly.mov(direction),
+            MetaObject::Poly(poly) => MetaObject::Poly(poly.rotated(rot)),
+            MetaObject::Rect(rect) if rot.sin != S::ZERO || rot.cos != S::ONE => {
+                let corners = rect.aabb().points().map(|it| it.rotate(rot)).collect();
+                MetaObject::Poly(PolyhedronObject::from_points(corners))
+            }
+            _ => self.clone(),
         }
     }
 
-    pub fn kick(&mut self, speed: Vector) {
+    /// Moment of inertia about the centre for a mass of 1.
+    pub fn unit_inertia(&self) -> S {
         match self {
-            MetaObject::Rect(rect) => rect.kick(speed),
-            MetaObject::Circle(circle) => circle.kick(speed),
-            MetaObject::Poly(poly) => poly.kick(speed),
+            MetaObject::Rect(rect) => rect.size.dot_sqr() / S::from_f64(12.0),
+            MetaObject::Circle(circle) => circle.radius * circle.radius * S::from_f64(0.5),
+            MetaObject::Poly(poly) => poly.unit_inertia(),
         }
     }
+}
 
-    pub fn aabb(&self) -> Rectangle {
-        match self {
-            MetaObject::Rect(rect) => rect.aabb(),
-            MetaObject::Circle(circle) => circle.aabb(),
-            MetaObject::Poly(poly) => poly.aabb(),
+/// A shape placed in world space. Polygon points are borrowed, so a `World`
+/// can keep the points of all its bodies in one buffer.
+#[derive(Clone, Copy, Debug)]
+pub enum Placed<'a, S = f64> {
+    Rect(Rectangle<S>),
+    Circle(Circle<S>),
+    Poly(Polygon<'a, S>),
+}
+
+impl<S: Scalar> Placed<'_, S> {
+    /// How far `other` has to move to stop overlapping this shape.
+    pub fn collides_with(&self, other: &Placed<'_, S>) -> Option<Vector<S>> {
+        match (self, other) {
+            (Placed::Rect(a), Placed::Rect(b)) => collide_rect_rect(a, b),
+            (Placed::Rect(a), Placed::Circle(b)) => collide_circle_rect(b, a).map(|x| -x),
+            (Placed::Rect(a), Placed::Poly(b)) => collide_poly_rect(b, a).map(|x| -x),
+            (Placed::Circle(a), Placed::Circle(b)) => collide_cirle_circle(a, b),
+            (Placed::Circle(a), Placed::Rect(b)) => collide_circle_rect(a, b),
+            (Placed::Circle(a), Placed::Poly(b)) => collide_poly_circle(b, a).map(|x| -x),
+            (Placed::Poly(a), Placed::Rect(b)) => collide_poly_rect(a, b),
+            (Placed::Poly(a), Placed::Circle(b)) => collide_poly_circle(a, b),
+            (Placed::Poly(a), Placed::Poly(b)) => collide_poly_poly(a, b),
         }
     }
 }
@@ -1,27 +1,27 @@
 use crate::{
-    collisions::{collide_poly_circle, collide_poly_poly, collide_poly_rect},
-    geometry::{Rectangle, Vector},
-    object::MetaObject,
+    geometry::{sin_cos, Polygon, Rectangle, Rot, Vector},
+    object::{MetaObject, Placed},
+    scalar::Scalar,
 };
 
-pub struct PolyhedronObject {
-    pub center: Vector,
-    pub points: Box<[Vector]>,
-    pub speed: Vector,
-    pub aabb: Rectangle,
+#[derive(Clone)]
+pub struct PolyhedronObject<S = f64> {
+    points: Box<[Vector<S>]>,
+    pub aabb: Rectangle<S>,
 }
 
-impl PolyhedronObject {
-    pub fn new(center: Vector, radius: f64, point_count: usize, speed: Vector) -> Self {
+impl<S: Scalar> PolyhedronObject<S> {
+    pub fn new(radius: S, point_count: usize) -> Self {
         let mut points = vec![Vector::zero(); point_count].into_boxed_slice();
 
         let mut angle = 0.0_f64;
         let angle_step = 2.0 * std::f64::consts::PI / point_count as f64;
 
-        let mut aabb = Rectangle::new(f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+        let mut aabb = Rectangle::new(S::MAX, S::MAX, S::MIN, S::MIN);
 
         for point in points.iter_mut() {
-            let p = Vector::new(angle.sin(), angle.cos()) * radius;
+            let (sin, cos) = sin_cos(angle);
+            let p = Vector::new(S::from_f64(sin), S::from_f64(cos)) * radius;
 
             if p.x < aabb.coord.x {
                 aabb.coord.x = p.x;
@@ -44,67 +44,72 @@ impl PolyhedronObject {
         let aabb_size = aabb.size - aabb.coord;
         aabb.size = aabb_size;
 
-        Self {
-            center,
-            points,
-            speed,
-            aabb,
-        }
+        Self { points, aabb }
     }
 
-    pub fn new_obj(center: Vector, radius: f64, point_count: usize, speed: Vector) -> MetaObject {
-        MetaObject::Poly(Self::new(center, radius, point_count, speed))
+    /// A polygon through `points`, given in order around the centre.
+    pub fn from_points(points: Box<[Vector<S>]>) -> Self {
+        let aabb = bounds(&points).unwrap_or(Rectangle::new_vec(Vector::zero(), Vector::zero()));
+        Self { points, aabb }
     }
 
-    pub fn tick(&mut self, delta_time: f64) {
-        self.center += self.speed() * delta_time;
+    pub fn new_obj(radius: S, point_count: usize) -> MetaObject<S> {
+        MetaObject::Poly(Self::new(radius, point_count))
     }
 
-    pub fn collides_with(&self, other: &MetaObject) -> Option<Vector> {
-        match other {
-            MetaObject::Rect(rect) => collide_poly_rect(self, rect),
-            MetaObject::Circle(circle) => collide_poly_circle(self, circle),
-            MetaObject::Poly(poly) => collide_poly_poly(self, poly),
-        }
+    pub fn at(&self, position: Vector<S>) -> Polygon<'_, S> {
+        Polygon::new(position, self.points())
     }
 
-    pub fn speed(&self) -> Vector {
-        self.speed
+    pub fn collides_with(
+        &self,
+        position: Vector<S>,
+        other: &MetaObject<S>,
+        other_position: Vector<S>,
+    ) -> Option<Vector<S>> {
+        Placed::Poly(self.at(position)).collides_with(&other.at(other_position))
     }
 
-    pub fn mov(&mut self, direction: Vector) {
-        self.center += direction;
+    pub fn aabb(&self) -> Rectangle<S> {
+        self.aabb
     }
 
-    pub fn kick(&mut self, speed: Vector) {
-        self.speed += speed
+    pub fn points(&self) -> &[Vector<S>] {
+        &self.points
     }
 
-    pub fn aabb(&self) -> Rectangle {
-        let mut aabb = self.aabb;
-        aabb.coord += self.center;
-        // log::info!("{:?}", aabb);
-        aabb
+    pub fn rotated(&self, rot: Rot<S>) -> Self {
+        let points: Box<[Vector<S>]> = self.points.iter().map(|it| it.rotate(rot)).collect();
+        let aabb = bounds(&points).unwrap_or(self.aabb);
+        Self { points, aabb }
     }
 
-    #[allow(clippy::needless_lifetimes)]
-    pub fn points<'a>(&'a self) -> impl Iterator<Item = Vector> + 'a {
-        self.points.iter().map(|it| *it + self.center)
-    }
-
-    pub fn points_len(&self) -> usize {
-        self.points.len()
+    /// Moment of inertia per unit of mass, summed over a fan of triangles
+    /// from the centre.
+    pub fn unit_inertia(&self) -> S {
+        let points = self.points();
+        let mut area = S::ZERO;
+        let mut moment = S::ZERO;
+        for (k, &a) in points.iter().enumerate() {
+            let b = points[(k + 1) % points.len()];
+            let cross = a.cross(&b);
+            area += cross;
+            moment += cross * (a.dot_sqr() + a.dot(&b) + b.dot_sqr());
+        }
+        if area == S::ZERO {
+            return S::ZERO;
+        }
+        moment / (area * S::from_f64(6.0))
     }
+}
 
-    pub fn lines(&self) -> impl Iterator<Item = Rectangle> + '_ {
-        (0..self.points.len()).map(|i| {
-            let j = (i + 1) % self.points.len();
-
-            let p_len = (self.points[i] - self.points[j]).len();
-            let p_vec = (self.points[i] - self.points[j]) * (1.0 / p_len);
-            let p_vec = Vector::new(-p_vec.y, p_vec.x);
-
-            Rectangle::new_vec(self.points[i] + self.center, p_vec)
-        })
-    }
+/// The smallest box around `points`, if there are any.
+pub(crate) fn bounds<S: Scalar>(points: &[Vector<S>]) -> Option<Rectangle<S>> {
+    let (&first, rest) = points.split_first()?;
+    Some(
+        rest.iter()
+            .fold(Rectangle::new_vec(first, Vector::zero()), |aabb, &point| {
+                aabb.union(&Rectangle::new_vec(point, Vector::zero()))
+            }),
+    )
 }
@@ -5,6 +5,7 @@ pub const MAX_IN_NODE: usize = 1;
 pub struct QuadTree {
     node: Node,
     aabb: Rectangle,
+    growable: bool,
 }
 
 impl QuadTree {
@@ -12,13 +13,61 @@ impl QuadTree {
         Self {
             node: Node::root(),
             aabb,
+            growable: false,
         }
     }
 
+    /// A tree whose root doubles towards any object inserted outside of it,
+    /// so objects far from the initial bounds still get their own nodes.
+    pub fn new_unbounded(aabb: Rectangle) -> Self {
+        Self {
+            growable: true,
+            ..Self::new(aabb)
+        }
+    }
+
+    pub fn aabb(&self) -> Rectangle {
+        self.aabb
+    }
+
     pub fn insert(&mut self, object_id: usize, aabb: Rectangle) {
+        if self.growable {
+            self.grow_to(aabb);
+        }
         self.node.insert((object_id, aabb), self.aabb);
     }
 
+    fn grow_to(&mut self, aabb: Rectangle) {
+        if !is_finite(aabb) || self.aabb.size.x <= 0.0 || self.aabb.size.y <= 0.0 {
+            return;
+        }
+        while !self.aabb.contains(&aabb) {
+            let grow_left = aabb.left() < self.aabb.left();
+            let grow_up = aabb.top() < self.aabb.top();
+
+            let mut coord = self.aabb.coord;
+            if grow_left {
+                coord.x -= self.aabb.size.x;
+            }
+            if grow_up {
+                coord.y -= self.aabb.size.y;
+            }
+
+            let grown = Rectangle::new_vec(coord, self.aabb.size * 2.0);
+            if !is_finite(grown) {
+                // Out of f64 range; whatever is left over stays in the root.
+                return;
+            }
+
+            let mut children = [Node::root(), Node::root(), Node::root(), Node::root()];
+            let old_idx = grow_left as usize + 2 * grow_up as usize;
+            children[old_idx] = std::mem::replace(&mut self.node, Node::root());
+            self.node.children = Some(Box::new(children));
+
+            self.aabb = grown;
+        }
+    }
+
     pub fn might_collide<'a>(
         &'a self,
         object_id: usize,
@@ -27,7 +76,20 @@ impl QuadTree {
         self.node.might_collide((object_id, aabb), self.aabb)
     }
 
+    /// Levels below the root; a tree that never split has depth 0.
+    pub fn depth(&self) -> usize {
+        self.node.depth()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.node.node_count()
+    }
+
     pub fn export(&self, vec: &mut Vec<f64>) {
+        vec.push(self.aabb.left());
+        vec.push(self.aabb.top());
+        vec.push(self.aabb.right());
+        vec.push(self.aabb.bottom());
         self.node.export(vec);
     }
 }
@@ -128,6 +190,20 @@ impl Node {
         )
     }
 
+    fn depth(&self) -> usize {
+        match &self.children {
+            None => 0,
+            Some(children) => 1 + children.iter().map(Node::depth).max().unwrap_or(0),
+        }
+    }
+
+    fn node_count(&self) -> usize {
+        match &self.children {
+            None => 1,
+            Some(children) => 1 + children.iter().map(Node::node_count).sum::<usize>(),
+        }
+    }
+
     fn export(&self, vec: &mut Vec<f64>) {
         match &self.children {
             None => vec.push(0.0),
@@ -141,6 +217,13 @@ impl Node {
     }
 }
 
+fn is_finite(aabb: Rectangle) -> bool {
+    aabb.coord.x.is_finite()
+        && aabb.coord.y.is_finite()
+        && aabb.size.x.is_finite()
+        && aabb.size.y.is_finite()
+}
+
 fn divide_aabb(aabb: Rectangle) -> [Rectangle; 4] {
     let half = aabb.size * 0.5;
     [
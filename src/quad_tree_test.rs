@@ -0,0 +1,52 @@
+use crate::{
+    geometry::{Rectangle, Vector},
+    quad_tree::QuadTree,
+};
+
+#[test]
+fn test_unbounded_tree_grows_to_contain_objects() {
+    let mut tree = QuadTree::new_unbounded(Rectangle::new(0.0, 0.0, 100.0, 100.0));
+    tree.insert(0, Rectangle::new(10.0, 10.0, 5.0, 5.0));
+    tree.insert(1, Rectangle::new(-250.0, 320.0, 5.0, 5.0));
+
+    let root = tree.aabb();
+    assert!(root.contains(&Rectangle::new(-250.0, 320.0, 5.0, 5.0)));
+    assert!(root.contains(&Rectangle::new(0.0, 0.0, 100.0, 100.0)));
+}
+
+#[test]
+fn test_unbounded_tree_finds_neighbours_outside_initial_bounds() {
+    let mut tree = QuadTree::new_unbounded(Rectangle::new(0.0, 0.0, 100.0, 100.0));
+    tree.insert(0, Rectangle::new(10.0, 10.0, 5.0, 5.0));
+    tree.insert(1, Rectangle::new(1000.0, 1000.0, 5.0, 5.0));
+    tree.insert(2, Rectangle::new(1003.0, 1003.0, 5.0, 5.0));
+    tree.insert(3, Rectangle::new(-700.0, 40.0, 5.0, 5.0));
+
+    let found: Vec<usize> = tree
+        .might_collide(1, Rectangle::new(1000.0, 1000.0, 5.0, 5.0))
+        .collect();
+    assert_eq!(found, vec![2]);
+}
+
+#[test]
+fn test_bounded_tree_keeps_its_root() {
+    let mut tree = QuadTree::new(Rectangle::new(0.0, 0.0, 100.0, 100.0));
+    tree.insert(0, Rectangle::new(1000.0, 1000.0, 5.0, 5.0));
+
+    let root = tree.aabb();
+    assert!((root.size - Vector::new(100.0, 100.0)).len() < 0.0000001);
+}
+
+#[test]
+fn test_unbounded_tree_stops_growing_at_the_f64_range() {
+    let mut tree = QuadTree::new_unbounded(Rectangle::new(0.0, 0.0, 100.0, 100.0));
+    let far = Rectangle::new(-1.79e308, 10.0, 5.0, 5.0);
+    tree.insert(0, Rectangle::new(10.0, 10.0, 5.0, 5.0));
+    tree.insert(1, far);
+    tree.insert(2, far);
+
+    let root = tree.aabb();
+    assert!(root.coord.x.is_finite() && root.size.x.is_finite());
+    let found: Vec<usize> = tree.might_collide(1, far).collect();
+    assert_eq!(found, vec![2]);
+}
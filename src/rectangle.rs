@@ -1,63 +1,39 @@
-use crate::collisions::{collide_circle_rect, collide_poly_rect, collide_rect_rect};
 use crate::geometry::{Rectangle, Vector};
-use crate::object::MetaObject;
+use crate::object::{MetaObject, Placed};
+use crate::scalar::Scalar;
 
-pub struct RectangleObject {
-    pub shape: Rectangle,
-    speed: Vector,
+#[derive(Clone)]
+pub struct RectangleObject<S = f64> {
+    pub size: Vector<S>,
 }
 
-impl RectangleObject {
-    pub fn new(x: f64, y: f64, w: f64, h: f64, speed: Vector) -> Self {
+impl<S: Scalar> RectangleObject<S> {
+    pub fn new(w: S, h: S) -> Self {
         Self {
-            shape: Rectangle::new(x, y, w, h),
-            speed,
+            size: Vector::new(w, h),
         }
     }
 
-    pub fn new_obj(x: f64, y: f64, w: f64, h: f64, speed: Vector) -> MetaObject {
-        MetaObject::Rect(Self::new(x, y, w, h, speed))
+    pub fn new_obj(w: S, h: S) -> MetaObject<S> {
+        MetaObject::Rect(Self::new(w, h))
     }
 
-    pub fn left(&self) -> f64 {
-        self.shape.left()
-    }
-
-    pub fn top(&self) -> f64 {
-        self.shape.top()
-    }
-
-    pub fn center(&self) -> Vector {
-        self.shape.center()
+    pub fn at(&self, position: Vector<S>) -> Rectangle<S> {
+        Rectangle::new_vec(position - self.size * S::from_f64(0.5), self.size)
     }
 }
 
-impl RectangleObject {
-    pub fn tick(&mut self, delta_time: f64) {
-        self.shape.coord += self.speed * delta_time;
-    }
-
-    pub fn collides_with(&self, other: &MetaObject) -> Option<Vector> {
-        match other {
-            MetaObject::Rect(rect) => collide_rect_rect(self, rect),
-            MetaObject::Circle(circle) => collide_circle_rect(circle, self).map(|x| x * -1.0),
-            MetaObject::Poly(poly) => collide_poly_rect(poly, self).map(|x| x * -1.0),
-        }
-    }
-
-    pub fn mov(&mut self, direction: Vector) {
-        self.shape.coord += direction;
-    }
-
-    pub fn kick(&mut self, speed: Vector) {
-        self.speed += speed;
-    }
-
-    pub fn speed(&self) -> Vector {
-        self.speed
+impl<S: Scalar> RectangleObject<S> {
+    pub fn collides_with(
+        &self,
+        position: Vector<S>,
+        other: &MetaObject<S>,
+        other_position: Vector<S>,
+    ) -> Option<Vector<S>> {
+        Placed::Rect(self.at(position)).collides_with(&other.at(other_position))
     }
 
-    pub fn aabb(&self) -> Rectangle {
-        self.shape
+    pub fn aabb(&self) -> Rectangle<S> {
+        self.at(Vector::zero())
     }
 }
@@ -0,0 +1,79 @@
+use crate::{body::BodyHandle, geometry::Vector};
+
+/// A touching pair about to be pushed apart. `normal` points from `a` towards
+/// `b` and `depth` is how far they have to move; both can be changed by the
+/// handler before the response is applied.
+#[derive(Clone, Copy, Debug)]
+pub struct Contact {
+    pub a: BodyHandle,
+    pub b: BodyHandle,
+    pub normal: Vector,
+    pub depth: f64,
+    pub destroy_a: bool,
+    pub destroy_b: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Response {
+    Resolve,
+    Ignore,
+}
+
+pub trait ContactHandler {
+    fn on_contact(&mut self, contact: &mut Contact) -> Response;
+}
+
+impl<F: FnMut(&mut Contact) -> Response> ContactHandler for F {
+    fn on_contact(&mut self, contact: &mut Contact) -> Response {
+        self(contact)
+    }
+}
+
+/// Pushes every pair apart and bounces it, nothing else.
+pub struct Bounce;
+
+impl ContactHandler for Bounce {
+    fn on_contact(&mut self, _contact: &mut Contact) -> Response {
+        Response::Resolve
+    }
+}
+
+/// Every contact costs both bodies a hit point, and a body is destroyed once
+/// it has none left.
+pub struct HealthPolicy {
+    initial: isize,
+    health: Vec<(u32, isize)>,
+}
+
+impl HealthPolicy {
+    pub fn new(initial: isize) -> Self {
+        Self {
+            initial,
+            health: Vec::new(),
+        }
+    }
+
+    pub fn health(&self, body: BodyHandle) -> isize {
+        match self.health.get(body.index) {
+            Some(&(generation, health)) if generation == body.generation => health,
+            _ => self.initial,
+        }
+    }
+
+    fn hit(&mut self, body: BodyHandle) -> bool {
+        if self.health.len() <= body.index {
+            self.health.resize(body.index + 1, (0, self.initial));
+        }
+        let health = self.health(body) - 1;
+        self.health[body.index] = (body.generation, health);
+        health <= 0
+    }
+}
+
+impl ContactHandler for HealthPolicy {
+    fn on_contact(&mut self, contact: &mut Contact) -> Response {
+        contact.destroy_a = self.hit(contact.a);
+        contact.destroy_b = self.hit(contact.b);
+        Response::Resolve
+    }
+}
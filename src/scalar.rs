@@ -0,0 +1,245 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use crate::geometry::{project_polygon_lanes_scalar, Polygon, Rectangle, Vector, LANES};
+
+/// The number type the geometry layer is built on: `f64` by default, `f32`
+/// for lighter builds and `Fixed` where results have to match bit for bit.
+pub trait Scalar:
+    Copy
+    + Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + Send
+    + Sync
+    + 'static
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+    fn sqrt(self) -> Self;
+    fn abs(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn atan2(self, x: Self) -> Self;
+
+    fn project_polygon_lanes(
+        lines: &[Rectangle<Self>],
+        polygon: &Polygon<Self>,
+    ) -> [Vector<Self>; LANES] {
+        project_polygon_lanes_scalar(lines, polygon)
+    }
+}
+
+impl Scalar for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const MIN: Self = f64::MIN;
+    const MAX: Self = f64::MAX;
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        f64::sin_cos(self)
+    }
+
+    fn atan2(self, x: Self) -> Self {
+        f64::atan2(self, x)
+    }
+
+    #[cfg(feature = "simd")]
+    fn project_polygon_lanes(
+        lines: &[Rectangle<Self>],
+        polygon: &Polygon<Self>,
+    ) -> [Vector<Self>; LANES] {
+        crate::geometry::project_polygon_lanes_simd(lines, polygon)
+    }
+}
+
+impl Scalar for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const MIN: Self = f32::MIN;
+    const MAX: Self = f32::MAX;
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        f32::sin_cos(self)
+    }
+
+    fn atan2(self, x: Self) -> Self {
+        f32::atan2(self, x)
+    }
+}
+
+/// A signed 32.32 fixed-point number. All of its operations are plain integer
+/// arithmetic, so it gives the same answer on every platform. Values run from
+/// about -2.1e9 to 2.1e9 in steps of 2^-32; results past that range, and
+/// division by zero, saturate to `MIN` or `MAX` instead of panicking.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(pub i64);
+
+impl Fixed {
+    pub const FRAC_BITS: u32 = 32;
+
+    pub fn from_bits(bits: i64) -> Self {
+        Self(bits)
+    }
+
+    pub fn to_bits(self) -> i64 {
+        self.0
+    }
+}
+
+impl Scalar for Fixed {
+    const ZERO: Self = Fixed(0);
+    const ONE: Self = Fixed(1 << Fixed::FRAC_BITS);
+    const MIN: Self = Fixed(i64::MIN);
+    const MAX: Self = Fixed(i64::MAX);
+
+    fn from_f64(value: f64) -> Self {
+        Fixed((value * (1u64 << Self::FRAC_BITS) as f64) as i64)
+    }
+
+    fn to_f64(self) -> f64 {
+        self.0 as f64 / (1u64 << Self::FRAC_BITS) as f64
+    }
+
+    fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Self::ZERO;
+        }
+        Fixed(isqrt((self.0 as u128) << Self::FRAC_BITS) as i64)
+    }
+
+    fn abs(self) -> Self {
+        Fixed(self.0.saturating_abs())
+    }
+
+    /// Goes through `f64`, so unlike the arithmetic it is not bit-exact
+    /// across platforms; keep it out of the per-tick path.
+    fn sin_cos(self) -> (Self, Self) {
+        let (sin, cos) = self.to_f64().sin_cos();
+        (Self::from_f64(sin), Self::from_f64(cos))
+    }
+
+    fn atan2(self, x: Self) -> Self {
+        Self::from_f64(self.to_f64().atan2(x.to_f64()))
+    }
+}
+
+fn saturate(value: i128) -> Fixed {
+    Fixed(value.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+}
+
+fn isqrt(value: u128) -> u128 {
+    let mut res = 0u128;
+    let mut bit = 1u128 << 126;
+    let mut value = value;
+    while bit > value {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if value >= res + bit {
+            value -= res + bit;
+            res = (res >> 1) + bit;
+        } else {
+            res >>= 1;
+        }
+        bit >>= 2;
+    }
+    res
+}
+
+impl Add for Fixed {
+    type Output = Self;
+    fn add(self, right: Self) -> Self {
+        Fixed(self.0.saturating_add(right.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+    fn sub(self, right: Self) -> Self {
+        Fixed(self.0.saturating_sub(right.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+    fn mul(self, right: Self) -> Self {
+        saturate((self.0 as i128 * right.0 as i128) >> Self::FRAC_BITS)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+    fn div(self, right: Self) -> Self {
+        if right.0 == 0 {
+            return match self.0.cmp(&0) {
+                Ordering::Less => Self::MIN,
+                Ordering::Equal => Self::ZERO,
+                Ordering::Greater => Self::MAX,
+            };
+        }
+        saturate(((self.0 as i128) << Self::FRAC_BITS) / right.0 as i128)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Fixed(self.0.saturating_neg())
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, right: Self) {
+        *self = *self + right;
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, right: Self) {
+        *self = *self - right;
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    collisions::{collide_cirle_circle, collide_rect_rect, one_dimensional_collision, Overlap},
+    geometry::{Circle, Rectangle, Vector},
+    object::MetaObject,
+    polyhedron::PolyhedronObject,
+    rectangle::RectangleObject,
+    scalar::{Fixed, Scalar},
+};
+
+fn fixed(value: f64) -> Fixed {
+    Fixed::from_f64(value)
+}
+
+#[test]
+fn test_fixed_arithmetic() {
+    assert_eq!(fixed(1.5) + fixed(2.25), fixed(3.75));
+    assert_eq!(fixed(1.5) * fixed(-2.0), fixed(-3.0));
+    assert_eq!(fixed(3.0) / fixed(4.0), fixed(0.75));
+    assert_eq!(fixed(6.25).sqrt(), fixed(2.5));
+    assert!((fixed(2.0).sqrt().to_f64() - 2f64.sqrt()).abs() < 1e-9);
+}
+
+#[test]
+fn test_fixed_saturates_at_its_range() {
+    let (min, max) = (Fixed::MIN, Fixed::MAX);
+    assert_eq!(max + Fixed::ONE, max);
+    assert_eq!(min - Fixed::ONE, min);
+    assert_eq!(-min, max);
+    assert_eq!(min.abs(), max);
+    assert_eq!(max * fixed(2.0), max);
+    assert_eq!(max * fixed(-2.0), min);
+    assert_eq!(fixed(2e9) / fixed(0.5), max);
+    assert_eq!(fixed(1.0) / Fixed::ZERO, max);
+    assert_eq!(fixed(-1.0) / Fixed::ZERO, min);
+    assert_eq!(Fixed::ZERO / Fixed::ZERO, Fixed::ZERO);
+
+    let mut value = max;
+    value += Fixed::ONE;
+    assert_eq!(value, max);
+    value = min;
+    value -= Fixed::ONE;
+    assert_eq!(value, min);
+}
+
+#[test]
+fn test_one_dimensional_collision_overlap() {
+    let overlap = |a: (f64, f64), b: (f64, f64)| {
+        one_dimensional_collision(Vector::new(a.0, a.1), Vector::new(b.0, b.1))
+    };
+    assert_eq!(overlap((0.0, 1.0), (2.0, 3.0)), Overlap::Separated);
+    assert_eq!(overlap((0.0, 3.0), (0.5, 2.0)), Overlap::Penetration(2.0));
+    assert_eq!(overlap((0.0, 2.0), (1.0, 3.0)), Overlap::Penetration(-1.0));
+}
+
+#[test]
+fn test_f32_and_fixed_match_f64() {
+    let a = Rectangle::new(0.0, 0.0, 10.0, 10.0);
+    let b = Rectangle::new(8.0, 3.0, 10.0, 10.0);
+    let expected = collide_rect_rect(&a, &b).unwrap();
+
+    let convert_f32 = |r: &Rectangle| Rectangle::new(r.left() as f32, r.top() as f32, 10.0, 10.0);
+    let res = collide_rect_rect(&convert_f32(&a), &convert_f32(&b)).unwrap();
+    assert!((res.x as f64 - expected.x).abs() < 1e-5);
+    assert!((res.y as f64 - expected.y).abs() < 1e-5);
+
+    let a = Circle::new(Vector::new(fixed(0.0), fixed(0.0)), fixed(5.0));
+    let b = Circle::new(Vector::new(fixed(8.0), fixed(0.0)), fixed(5.0));
+    let res = collide_cirle_circle(&a, &b).unwrap();
+    assert_eq!(res.x, fixed(2.0));
+    assert_eq!(res.y, fixed(0.0));
+}
+
+#[test]
+fn test_fixed_polygon_collision() {
+    let poly: MetaObject<Fixed> = PolyhedronObject::new_obj(fixed(10.0), 6);
+    let rect = RectangleObject::new_obj(fixed(20.0), fixed(20.0));
+
+    let position = Vector::new(fixed(0.0), fixed(0.0));
+    let near = Vector::new(fixed(15.0), fixed(3.0));
+    let far = Vector::new(fixed(40.0), fixed(3.0));
+
+    let res = poly.collides_with(position, &rect, near).unwrap();
+    assert!(res.x.to_f64() > 0.0);
+    assert!(poly.collides_with(position, &rect, far).is_none());
+}
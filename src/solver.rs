@@ -0,0 +1,285 @@
+use crate::{geometry::Vector, joint::JointKind};
+
+/// Tuning for the contact solver in `World::tick`.
+#[derive(Clone, Copy, Debug)]
+pub struct SolverConfig {
+    /// Passes over all contacts per tick, for both velocities and positions.
+    pub iterations: usize,
+    /// Share of the overlap beyond `slop` removed per tick.
+    pub position_correction: f64,
+    /// Overlap left alone so resting contacts stay in touch.
+    pub slop: f64,
+    /// Closing speeds below this do not bounce, which lets stacks settle.
+    pub restitution_threshold: f64,
+    /// Start each contact from the impulse it ended the previous tick with.
+    pub warm_starting: bool,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 8,
+            position_correction: 0.2,
+            slop: 0.5,
+            restitution_threshold: 1.0,
+            warm_starting: true,
+        }
+    }
+}
+
+/// A contact between the bodies at slots `a` and `b`, with `normal` pointing
+/// from `a` towards `b`. The impulses are accumulated over the iterations and
+/// cached by `World` for warm starting.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SolverContact {
+    pub a: usize,
+    pub b: usize,
+    pub normal: Vector,
+    pub depth: f64,
+    pub static_friction: f64,
+    pub dynamic_friction: f64,
+    pub restitution: f64,
+    pub normal_impulse: f64,
+    pub tangent_impulse: f64,
+}
+
+/// A joint between the bodies at slots `a` and `b`, with everything turned
+/// into world space for this tick.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SolverJoint {
+    pub a: usize,
+    pub b: usize,
+    /// Anchors relative to each body's centre.
+    pub offset_a: Vector,
+    pub offset_b: Vector,
+    /// From `a`'s anchor to `b`'s.
+    pub separation: Vector,
+    /// `b`'s angle minus `a`'s.
+    pub angle: f64,
+    pub kind: JointKind,
+}
+
+/// The parts of `World` the solver works on, indexed by body slot.
+pub(crate) struct Bodies<'a> {
+    pub positions: &'a mut [Vector],
+    pub speeds: &'a mut [Vector],
+    pub angular_speeds: &'a mut [f64],
+    pub inv_masses: &'a [f64],
+    pub inv_inertias: &'a [f64],
+}
+
+/// Sequential impulses over `contacts` and `joints`. Contact overlap is fixed
+/// by a separate pass that moves positions directly (split impulse), so
+/// pushing bodies apart never adds to their speed; joints drift back with a
+/// Baumgarte bias on their speed instead.
+pub(crate) fn solve(
+    contacts: &mut [SolverContact],
+    joints: &[SolverJoint],
+    mut bodies: Bodies,
+    config: &SolverConfig,
+    delta_time: f64,
+) {
+    let inv_masses = bodies.inv_masses;
+    let speeds = &mut *bodies.speeds;
+
+    let mut bounces = Vec::with_capacity(contacts.len());
+    for contact in contacts.iter_mut() {
+        let closing = relative_speed(contact, speeds).dot(&contact.normal);
+        bounces.push(if closing < -config.restitution_threshold {
+            -contact.restitution * closing
+        } else {
+            0.0
+        });
+
+        if !config.warm_starting {
+            contact.normal_impulse = 0.0;
+            contact.tangent_impulse = 0.0;
+        }
+        let impulse = contact.normal * contact.normal_impulse
+            + contact.normal.perp() * contact.tangent_impulse;
+        apply(contact, speeds, inv_masses, impulse);
+    }
+
+    for joint in joints.iter() {
+        if let JointKind::Spring {
+            length,
+            stiffness,
+            damping,
+        } = joint.kind
+        {
+            bodies.spring(joint, length, stiffness, damping, delta_time);
+        }
+    }
+
+    let correction = if delta_time > 0.0 {
+        config.position_correction / delta_time
+    } else {
+        0.0
+    };
+    for _ in 0..config.iterations {
+        let speeds = &mut *bodies.speeds;
+        for (contact, &bounce) in contacts.iter_mut().zip(bounces.iter()) {
+            let inv_mass = inv_masses[contact.a] + inv_masses[contact.b];
+            if inv_mass == 0.0 {
+                continue;
+            }
+            let tangent = contact.normal.perp();
+
+            let closing = relative_speed(contact, speeds).dot(&contact.normal);
+            let total = (contact.normal_impulse + (bounce - closing) / inv_mass).max(0.0);
+            let delta = total - contact.normal_impulse;
+            contact.normal_impulse = total;
+            apply(contact, speeds, inv_masses, contact.normal * delta);
+
+            let sliding = relative_speed(contact, speeds).dot(&tangent);
+            let mut total = contact.tangent_impulse - sliding / inv_mass;
+            if total.abs() > contact.static_friction * contact.normal_impulse {
+                let limit = contact.dynamic_friction * contact.normal_impulse;
+                total = total.clamp(-limit, limit);
+            }
+            let delta = total - contact.tangent_impulse;
+            contact.tangent_impulse = total;
+            apply(contact, speeds, inv_masses, tangent * delta);
+        }
+        for joint in joints.iter() {
+            bodies.joint(joint, correction);
+        }
+    }
+
+    let positions = &mut *bodies.positions;
+    let mut shifts = vec![Vector::zero(); positions.len()];
+    let mut pushed = vec![0.0; contacts.len()];
+    for _ in 0..config.iterations {
+        for (contact, pushed) in contacts.iter().zip(pushed.iter_mut()) {
+            let inv_mass = inv_masses[contact.a] + inv_masses[contact.b];
+            if inv_mass == 0.0 {
+                continue;
+            }
+            let target = config.position_correction * (contact.depth - config.slop).max(0.0);
+            let moved = (shifts[contact.b] - shifts[contact.a]).dot(&contact.normal);
+            let total = (*pushed + (target - moved) / inv_mass).max(0.0);
+            let delta = contact.normal * (total - *pushed);
+            *pushed = total;
+            shifts[contact.a] -= delta * inv_masses[contact.a];
+            shifts[contact.b] += delta * inv_masses[contact.b];
+        }
+    }
+    for contact in contacts.iter() {
+        for index in [contact.a, contact.b] {
+            positions[index] += shifts[index];
+            shifts[index] = Vector::zero();
+        }
+    }
+}
+
+fn relative_speed(contact: &SolverContact, speeds: &[Vector]) -> Vector {
+    speeds[contact.b] - speeds[contact.a]
+}
+
+fn apply(contact: &SolverContact, speeds: &mut [Vector], inv_masses: &[f64], impulse: Vector) {
+    speeds[contact.a] -= impulse * inv_masses[contact.a];
+    speeds[contact.b] += impulse * inv_masses[contact.b];
+}
+
+impl Bodies<'_> {
+    fn joint(&mut self, joint: &SolverJoint, correction: f64) {
+        match joint.kind {
+            JointKind::Distance { length } => {
+                if let Some(direction) = joint.separation.try_norm() {
+                    let error = joint.separation.len() - length;
+                    self.along(joint, direction, error * correction);
+                }
+            }
+            JointKind::Revolute => self.point(joint, joint.separation * correction),
+            JointKind::Prismatic { axis, angle } => {
+                let across = axis.perp();
+                self.turn(joint, (joint.angle - angle) * correction);
+                self.along(joint, across, across.dot(&joint.separation) * correction);
+            }
+            JointKind::Weld { angle } => {
+                self.turn(joint, (joint.angle - angle) * correction);
+                self.point(joint, joint.separation * correction);
+            }
+            JointKind::Spring { .. } => {}
+        }
+    }
+
+    /// An explicit damped spring force, applied once per tick.
+    fn spring(
+        &mut self,
+        joint: &SolverJoint,
+        length: f64,
+        stiffness: f64,
+        damping: f64,
+        delta_time: f64,
+    ) {
+        let direction = match joint.separation.try_norm() {
+            Some(direction) => direction,
+            None => return,
+        };
+        let stretch = joint.separation.len() - length;
+        let rate = self.relative_speed(joint).dot(&direction);
+        let force = -(stiffness * stretch + damping * rate);
+        self.apply(joint, direction * (force * delta_time));
+    }
+
+    /// Stops the anchors moving apart along `direction`, plus `bias`.
+    fn along(&mut self, joint: &SolverJoint, direction: Vector, bias: f64) {
+        let (ma, mb) = (self.inv_masses[joint.a], self.inv_masses[joint.b]);
+        let (ia, ib) = (self.inv_inertias[joint.a], self.inv_inertias[joint.b]);
+        let (ca, cb) = (
+            joint.offset_a.cross(&direction),
+            joint.offset_b.cross(&direction),
+        );
+        let mass = ma + mb + ia * ca * ca + ib * cb * cb;
+        if mass == 0.0 {
+            return;
+        }
+        let rate = self.relative_speed(joint).dot(&direction);
+        self.apply(joint, direction * (-(rate + bias) / mass));
+    }
+
+    /// Stops the anchors moving apart in any direction, plus `bias`.
+    fn point(&mut self, joint: &SolverJoint, bias: Vector) {
+        let (ma, mb) = (self.inv_masses[joint.a], self.inv_masses[joint.b]);
+        let (ia, ib) = (self.inv_inertias[joint.a], self.inv_inertias[joint.b]);
+        let (ra, rb) = (joint.offset_a, joint.offset_b);
+        let xx = ma + mb + ia * ra.y * ra.y + ib * rb.y * rb.y;
+        let xy = -ia * ra.x * ra.y - ib * rb.x * rb.y;
+        let yy = ma + mb + ia * ra.x * ra.x + ib * rb.x * rb.x;
+        let det = xx * yy - xy * xy;
+        if det == 0.0 {
+            return;
+        }
+        let rhs = -(self.relative_speed(joint) + bias);
+        let impulse = Vector::new(yy * rhs.x - xy * rhs.y, xx * rhs.y - xy * rhs.x) / det;
+        self.apply(joint, impulse);
+    }
+
+    /// Stops the bodies turning relative to each other, plus `bias`.
+    fn turn(&mut self, joint: &SolverJoint, bias: f64) {
+        let (ia, ib) = (self.inv_inertias[joint.a], self.inv_inertias[joint.b]);
+        if ia + ib == 0.0 {
+            return;
+        }
+        let rate = self.angular_speeds[joint.b] - self.angular_speeds[joint.a];
+        let impulse = -(rate + bias) / (ia + ib);
+        self.angular_speeds[joint.a] -= impulse * ia;
+        self.angular_speeds[joint.b] += impulse * ib;
+    }
+
+    fn relative_speed(&self, joint: &SolverJoint) -> Vector {
+        let point = |index: usize, offset: Vector| {
+            self.speeds[index] + offset.perp() * self.angular_speeds[index]
+        };
+        point(joint.b, joint.offset_b) - point(joint.a, joint.offset_a)
+    }
+
+    fn apply(&mut self, joint: &SolverJoint, impulse: Vector) {
+        let (a, b) = (joint.a, joint.b);
+        self.speeds[a] -= impulse * self.inv_masses[a];
+        self.angular_speeds[a] -= joint.offset_a.cross(&impulse) * self.inv_inertias[a];
+        self.speeds[b] += impulse * self.inv_masses[b];
+        self.angular_speeds[b] += joint.offset_b.cross(&impulse) * self.inv_inertias[b];
+    }
+}
@@ -0,0 +1,61 @@
+/// What one `World::tick` did.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StepStats {
+    /// Pairs the quad tree handed to the narrow phase.
+    pub candidates: usize,
+    /// Candidates dropped because their AABBs do not overlap.
+    pub aabb_rejections: usize,
+    /// Candidates that went through a shape test.
+    pub narrow_tests: usize,
+    /// Shape tests that found an overlap.
+    pub contacts: usize,
+    pub tree_depth: usize,
+    pub tree_nodes: usize,
+    /// All zero unless the `timings` feature is on.
+    pub timings: PhaseTimings,
+}
+
+/// Wall-clock seconds spent in each phase of a tick.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PhaseTimings {
+    pub broad: f64,
+    pub narrow: f64,
+    pub response: f64,
+    pub integrate: f64,
+}
+
+impl PhaseTimings {
+    pub fn total(&self) -> f64 {
+        self.broad + self.narrow + self.response + self.integrate
+    }
+}
+
+/// A source of time in seconds. `std::time::Instant` panics in the browser,
+/// so wasm builds plug in `performance.now()` instead.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> f64;
+}
+
+impl<F: Fn() -> f64 + Send + Sync> Clock for F {
+    fn now(&self) -> f64 {
+        self()
+    }
+}
+
+/// Seconds since the clock was created. Not available on wasm.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct StdClock(std::time::Instant);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for StdClock {
+    fn default() -> Self {
+        Self(std::time::Instant::now())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for StdClock {
+    fn now(&self) -> f64 {
+        self.0.elapsed().as_secs_f64()
+    }
+}
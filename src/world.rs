@@ -1,24 +1,86 @@
-use rand::Rng;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
 
 use crate::{
+    body::{BodyHandle, BodyType, Material},
+    boundary::{self, Boundary},
     circle::CircleObject,
-    geometry::{Rectangle, Vector},
-    object::MetaObject,
-    polyhedron::PolyhedronObject,
+    events::{CollisionEvent, OverlapPhase, ShapeHit},
+    filter::CollisionFilter,
+    geometry::{sin_cos, Polygon, Rectangle, Rot, Vector},
+    island::{self, SleepConfig},
+    joint::{Joint, JointHandle, JointKind},
+    object::{MetaObject, Placed},
+    polyhedron::{self, PolyhedronObject},
     quad_tree::QuadTree,
     rectangle::RectangleObject,
+    response::{Bounce, Contact, ContactHandler, Response},
+    solver::{self, Bodies, SolverConfig, SolverContact, SolverJoint},
+    stats::StepStats,
 };
 
 pub struct World {
-    objects: Vec<MetaObject>,
-    ticked: Vec<bool>,
-    health: Vec<isize>,
-    tick: bool,
+    positions: Vec<Vector>,
+    speeds: Vec<Vector>,
+    forces: Vec<Vector>,
+    angles: Vec<f64>,
+    angular_speeds: Vec<f64>,
+    torques: Vec<f64>,
+    aabbs: Vec<Rectangle>,
+    local_aabbs: Vec<Rectangle>,
+    shapes: Vec<MetaObject>,
+    /// Turned polygon points of every body, each owning a span of them.
+    points: Vec<Vector>,
+    point_spans: Vec<Range<usize>>,
+    alive: Vec<bool>,
+    generations: Vec<u32>,
+    free: Vec<usize>,
+    filters: Vec<CollisionFilter>,
+    materials: Vec<Material>,
+    body_types: Vec<BodyType>,
+    masses: Vec<f64>,
+    inv_masses: Vec<f64>,
+    inv_inertias: Vec<f64>,
+    linear_damping: Vec<f64>,
+    angular_damping: Vec<f64>,
+    sensors: Vec<bool>,
+    one_way: Vec<Option<Vector>>,
+    quarantined: Vec<bool>,
+    sleeping: Vec<bool>,
+    sleep_timers: Vec<f64>,
+    islands: Vec<usize>,
+    quarantine_reports: Vec<BodyHandle>,
+    exited: Vec<BodyHandle>,
+    contacts: BTreeMap<(BodyHandle, BodyHandle), CollisionEvent>,
+    passing: BTreeSet<(BodyHandle, BodyHandle)>,
+    events: Vec<CollisionEvent>,
+    impulses: BTreeMap<(BodyHandle, BodyHandle), (f64, f64)>,
+    joints: BTreeMap<JointHandle, Joint>,
+    next_joint: u64,
+    solver: SolverConfig,
+    sleep: SleepConfig,
+    resting_tree: Option<QuadTree>,
+    gravity: Vector,
     size: Vector,
+    boundary: Boundary,
+    #[cfg(feature = "timings")]
+    clock: Box<dyn crate::stats::Clock>,
 }
 
 impl World {
     pub fn new(width: f64, height: f64) -> Self {
+        Self::new_seeded(width, height, rand::thread_rng().gen())
+    }
+
+    /// The demo scene, generated from `seed` so every peer can build the same
+    /// one. ChaCha is used because, unlike `StdRng`, its output is pinned
+    /// across platforms and rand releases.
+    pub fn new_seeded(width: f64, height: f64, seed: u64) -> Self {
         let speed_rng = (30.0, 50.1);
         let size_rng = (1.0, 200.0);
         let rects = 1500;
@@ -26,16 +88,16 @@ impl World {
         let polyhedrons = 1500;
 
         let mut objects = vec![
-            // CircleObject::new_obj(Vector::new(200.0, 200.0), 50.0, Vector::new(-5.0, -5.0)),
-            // PolyhedronObject::new_obj(Vector::new(60.0, 60.0), 50.0, 3, Vector::new(5.0, 5.0)),
-            // CircleObject::new_obj(Vector::new(60.0, 60.0), 50.0, Vector::new(2.0, 2.0)),
-            // RectangleObject::new_obj(100.0, 100.0, 30.0, 30.0, Vector::new(0.0, 0.0)),
-            // RectangleObject::new_obj(10.0, 10.0, 30.0, 30.0, Vector::new(20.0, 20.0)),
-            // PolyhedronObject::new_obj(Vector::new(20.0, 20.0), 30.0, 3, Vector::new(10.0, 10.0)),
-            // CircleObject::new_obj(Vector::new(140.0, 140.0), 10.0, Vector::new(-10.0, -10.0)),
+            // (CircleObject::new_obj(50.0), Vector::new(200.0, 200.0), Vector::new(-5.0, -5.0)),
+            // (PolyhedronObject::new_obj(50.0, 3), Vector::new(60.0, 60.0), Vector::new(5.0, 5.0)),
+            // (CircleObject::new_obj(50.0), Vector::new(60.0, 60.0), Vector::new(2.0, 2.0)),
+            // (RectangleObject::new_obj(30.0, 30.0), Vector::new(115.0, 115.0), Vector::new(0.0, 0.0)),
+            // (RectangleObject::new_obj(30.0, 30.0), Vector::new(25.0, 25.0), Vector::new(20.0, 20.0)),
+            // (PolyhedronObject::new_obj(30.0, 3), Vector::new(20.0, 20.0), Vector::new(10.0, 10.0)),
+            // (CircleObject::new_obj(10.0), Vector::new(140.0, 140.0), Vector::new(-10.0, -10.0)),
         ];
         {
-            let mut rng = rand::thread_rng();
+            let mut rng = ChaChaRng::seed_from_u64(seed);
             for _ in 0..rects {
                 let size = rng.gen_range(size_rng.0, size_rng.1);
                 let speed = Vector::new(
@@ -43,11 +105,12 @@ impl World {
                     rng.gen_range(speed_rng.0, speed_rng.1),
                 ) * rng.gen_range(-1.0_f64, 1.0).signum();
 
-                objects.push(RectangleObject::new_obj(
-                    rng.gen_range(0.0, width - size),
-                    rng.gen_range(0.0, height - size),
-                    size,
-                    size,
+                objects.push((
+                    RectangleObject::new_obj(size, size),
+                    Vector::new(
+                        rng.gen_range(size / 2.0, width - size / 2.0),
+                        rng.gen_range(size / 2.0, height - size / 2.0),
+                    ),
                     speed,
                 ))
             }
@@ -58,12 +121,12 @@ impl World {
                     rng.gen_range(speed_rng.0, speed_rng.1),
                 ) * rng.gen_range(-1.0_f64, 1.0).signum();
 
-                objects.push(CircleObject::new_obj(
+                objects.push((
+                    CircleObject::new_obj(size),
                     Vector::new(
                         rng.gen_range(size / 2.0, width - size / 2.0),
                         rng.gen_range(size / 2.0, height - size / 2.0),
                     ),
-                    size,
                     speed,
                 ))
             }
@@ -74,72 +137,616 @@ impl World {
                     rng.gen_range(speed_rng.0, speed_rng.1),
                 ) * rng.gen_range(-1.0_f64, 1.0).signum();
 
-                objects.push(PolyhedronObject::new_obj(
+                objects.push((
+                    PolyhedronObject::new_obj(size, rng.gen_range(3, 10)),
                     Vector::new(
                         rng.gen_range(size / 2.0, width - size / 2.0),
                         rng.gen_range(size / 2.0, height - size / 2.0),
                     ),
-                    size,
-                    rng.gen_range(3, 10),
                     speed,
                 ))
             }
         }
+
+        let mut world = Self::empty(width, height);
+        for (shape, position, speed) in objects {
+            world.add_body(shape, position, speed);
+        }
+        world
+    }
+
+    pub fn empty(width: f64, height: f64) -> Self {
         Self {
-            ticked: vec![false; objects.len()],
-            health: vec![3; objects.len()],
-            tick: true,
-            objects,
+            positions: Vec::new(),
+            speeds: Vec::new(),
+            forces: Vec::new(),
+            angles: Vec::new(),
+            angular_speeds: Vec::new(),
+            torques: Vec::new(),
+            aabbs: Vec::new(),
+            local_aabbs: Vec::new(),
+            shapes: Vec::new(),
+            points: Vec::new(),
+            point_spans: Vec::new(),
+            alive: Vec::new(),
+            generations: Vec::new(),
+            free: Vec::new(),
+            filters: Vec::new(),
+            materials: Vec::new(),
+            body_types: Vec::new(),
+            masses: Vec::new(),
+            inv_masses: Vec::new(),
+            inv_inertias: Vec::new(),
+            linear_damping: Vec::new(),
+            angular_damping: Vec::new(),
+            sensors: Vec::new(),
+            one_way: Vec::new(),
+            quarantined: Vec::new(),
+            sleeping: Vec::new(),
+            sleep_timers: Vec::new(),
+            islands: Vec::new(),
+            quarantine_reports: Vec::new(),
+            exited: Vec::new(),
+            contacts: BTreeMap::new(),
+            passing: BTreeSet::new(),
+            events: Vec::new(),
+            impulses: BTreeMap::new(),
+            joints: BTreeMap::new(),
+            next_joint: 0,
+            solver: SolverConfig::default(),
+            sleep: SleepConfig::default(),
+            resting_tree: None,
+            gravity: Vector::zero(),
             size: Vector::new(width, height),
+            boundary: Boundary::default(),
+            #[cfg(feature = "timings")]
+            clock: default_clock(),
         }
     }
 
-    pub fn export_quad_tree(&self) -> Box<[f64]> {
-        let mut quad_tree = QuadTree::new(Rectangle::new_vec(Vector::zero(), self.size));
-        for (id, obj) in self.objects.iter().enumerate() {
-            if self.health[id] <= 0 {
+    pub fn add_body(&mut self, shape: MetaObject, position: Vector, speed: Vector) -> BodyHandle {
+        let local_aabb = shape.aabb();
+        let aabb = translate(local_aabb, position);
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.positions[index] = position;
+                self.speeds[index] = speed;
+                self.forces[index] = Vector::zero();
+                self.angles[index] = 0.0;
+                self.angular_speeds[index] = 0.0;
+                self.torques[index] = 0.0;
+                self.aabbs[index] = aabb;
+                self.local_aabbs[index] = local_aabb;
+                self.shapes[index] = shape;
+                self.alive[index] = true;
+                self.filters[index] = CollisionFilter::default();
+                self.materials[index] = Material::default();
+                self.body_types[index] = BodyType::Dynamic;
+                self.masses[index] = 1.0;
+                self.linear_damping[index] = 0.0;
+                self.angular_damping[index] = 0.0;
+                self.sensors[index] = false;
+                self.one_way[index] = None;
+                self.quarantined[index] = false;
+                self.sleeping[index] = false;
+                self.sleep_timers[index] = 0.0;
+                self.islands[index] = index;
+                index
+            }
+            None => {
+                self.positions.push(position);
+                self.speeds.push(speed);
+                self.forces.push(Vector::zero());
+                self.angles.push(0.0);
+                self.angular_speeds.push(0.0);
+                self.torques.push(0.0);
+                self.aabbs.push(aabb);
+                self.local_aabbs.push(local_aabb);
+                self.shapes.push(shape);
+                self.point_spans.push(0..0);
+                self.alive.push(true);
+                self.generations.push(0);
+                self.filters.push(CollisionFilter::default());
+                self.materials.push(Material::default());
+                self.body_types.push(BodyType::Dynamic);
+                self.masses.push(1.0);
+                self.inv_masses.push(0.0);
+                self.inv_inertias.push(0.0);
+                self.linear_damping.push(0.0);
+                self.angular_damping.push(0.0);
+                self.sensors.push(false);
+                self.one_way.push(None);
+                self.quarantined.push(false);
+                self.sleeping.push(false);
+                self.sleep_timers.push(0.0);
+                self.islands.push(self.shapes.len() - 1);
+                self.shapes.len() - 1
+            }
+        };
+        self.reserve_points(index);
+        self.pose(index);
+        self.update_inv_mass(index);
+        self.handle(index)
+    }
+
+    /// Frees the body's slot for reuse. Returns `false` if the handle was stale.
+    pub fn remove_body(&mut self, handle: BodyHandle) -> bool {
+        match self.index(handle) {
+            Some(index) => {
+                self.remove_index(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_alive(&self, handle: BodyHandle) -> bool {
+        self.index(handle).is_some()
+    }
+
+    /// The shape in the body's own space; `angle` says how it is turned.
+    pub fn shape(&self, handle: BodyHandle) -> Option<&MetaObject> {
+        self.index(handle).map(|index| &self.shapes[index])
+    }
+
+    pub fn position(&self, handle: BodyHandle) -> Option<Vector> {
+        self.index(handle).map(|index| self.positions[index])
+    }
+
+    pub fn set_position(&mut self, handle: BodyHandle, position: Vector) {
+        if let Some(index) = self.index(handle) {
+            self.positions[index] = position;
+            self.update_aabb(index);
+            self.moved(index);
+        }
+    }
+
+    pub fn speed(&self, handle: BodyHandle) -> Option<Vector> {
+        self.index(handle).map(|index| self.speeds[index])
+    }
+
+    pub fn set_speed(&mut self, handle: BodyHandle, speed: Vector) {
+        if let Some(index) = self.index(handle) {
+            self.speeds[index] = speed;
+            self.wake_index(index);
+        }
+    }
+
+    /// Radians, turning from +x towards +y.
+    pub fn angle(&self, handle: BodyHandle) -> Option<f64> {
+        self.index(handle).map(|index| self.angles[index])
+    }
+
+    pub fn set_angle(&mut self, handle: BodyHandle, angle: f64) {
+        if let Some(index) = self.index(handle) {
+            self.angles[index] = angle;
+            self.pose(index);
+            self.moved(index);
+        }
+    }
+
+    pub fn angular_speed(&self, handle: BodyHandle) -> Option<f64> {
+        self.index(handle).map(|index| self.angular_speeds[index])
+    }
+
+    pub fn set_angular_speed(&mut self, handle: BodyHandle, angular_speed: f64) {
+        if let Some(index) = self.index(handle) {
+            self.angular_speeds[index] = angular_speed;
+            self.wake_index(index);
+        }
+    }
+
+    /// Pushes the body during the next tick. Forces add up until then.
+    pub fn apply_force(&mut self, handle: BodyHandle, force: Vector) {
+        if let Some(index) = self.index(handle) {
+            self.forces[index] += force;
+            self.wake_index(index);
+        }
+    }
+
+    pub fn apply_torque(&mut self, handle: BodyHandle, torque: f64) {
+        if let Some(index) = self.index(handle) {
+            self.torques[index] += torque;
+            self.wake_index(index);
+        }
+    }
+
+    /// Changes the speed at once, as a hit would.
+    pub fn apply_impulse(&mut self, handle: BodyHandle, impulse: Vector) {
+        if let Some(index) = self.index(handle) {
+            self.speeds[index] += impulse * self.inv_masses[index];
+            self.wake_index(index);
+        }
+    }
+
+    /// Share of the speed lost per second, for movement and for turning.
+    pub fn set_damping(&mut self, handle: BodyHandle, linear: f64, angular: f64) {
+        if let Some(index) = self.index(handle) {
+            self.linear_damping[index] = linear;
+            self.angular_damping[index] = angular;
+        }
+    }
+
+    pub fn aabb(&self, handle: BodyHandle) -> Option<Rectangle> {
+        self.index(handle).map(|index| self.aabbs[index])
+    }
+
+    pub fn bodies(&self) -> impl Iterator<Item = BodyHandle> + '_ {
+        (0..self.shapes.len())
+            .filter(|&index| self.alive[index])
+            .map(|index| self.handle(index))
+    }
+
+    pub fn set_filter(&mut self, handle: BodyHandle, filter: CollisionFilter) {
+        if let Some(index) = self.index(handle) {
+            self.filters[index] = filter;
+        }
+    }
+
+    pub fn filter(&self, handle: BodyHandle) -> Option<CollisionFilter> {
+        self.index(handle).map(|index| self.filters[index])
+    }
+
+    pub fn set_material(&mut self, handle: BodyHandle, material: Material) {
+        if let Some(index) = self.index(handle) {
+            self.materials[index] = material;
+        }
+    }
+
+    pub fn material(&self, handle: BodyHandle) -> Option<Material> {
+        self.index(handle).map(|index| self.materials[index])
+    }
+
+    /// Bodies start with a mass of 1. The mass must be positive; zero,
+    /// negative and NaN masses are ignored. `f64::INFINITY` makes a body that
+    /// contacts and gravity never move, though `BodyType::Static` or
+    /// `BodyType::Kinematic` usually says that better.
+    pub fn set_mass(&mut self, handle: BodyHandle, mass: f64) {
+        if mass.is_nan() || mass <= 0.0 {
+            log::warn!("ignored mass {} for {:?}", mass, handle);
+            return;
+        }
+        if let Some(index) = self.index(handle) {
+            self.masses[index] = mass;
+            self.update_inv_mass(index);
+        }
+    }
+
+    pub fn mass(&self, handle: BodyHandle) -> Option<f64> {
+        self.index(handle).map(|index| self.masses[index])
+    }
+
+    /// Static and kinematic bodies act as if their mass were infinite.
+    /// Making a body static also stops it.
+    pub fn set_body_type(&mut self, handle: BodyHandle, body_type: BodyType) {
+        if let Some(index) = self.index(handle) {
+            self.body_types[index] = body_type;
+            if body_type == BodyType::Static {
+                self.speeds[index] = Vector::zero();
+                self.angular_speeds[index] = 0.0;
+            }
+            self.update_inv_mass(index);
+            self.wake_index(index);
+            self.resting_tree = None;
+        }
+    }
+
+    pub fn body_type(&self, handle: BodyHandle) -> Option<BodyType> {
+        self.index(handle).map(|index| self.body_types[index])
+    }
+
+    /// Sensors only show up in `drain_events` instead of bouncing off and
+    /// damaging whatever they touch.
+    pub fn set_sensor(&mut self, handle: BodyHandle, sensor: bool) {
+        if let Some(index) = self.index(handle) {
+            self.sensors[index] = sensor;
+        }
+    }
+
+    pub fn is_sensor(&self, handle: BodyHandle) -> bool {
+        self.index(handle).is_some_and(|index| self.sensors[index])
+    }
+
+    /// Makes the body solid only from the side `direction` points to, like a
+    /// platform that can be jumped through from below. A body that first
+    /// touches it from any other side passes through until they separate.
+    pub fn set_one_way(&mut self, handle: BodyHandle, direction: Option<Vector>) {
+        if let Some(index) = self.index(handle) {
+            self.one_way[index] = direction;
+        }
+    }
+
+    pub fn one_way(&self, handle: BodyHandle) -> Option<Vector> {
+        self.index(handle).and_then(|index| self.one_way[index])
+    }
+
+    /// Bodies whose position or speed stopped being finite are taken out of
+    /// the simulation instead of spreading NaNs to everything they touch.
+    pub fn is_quarantined(&self, handle: BodyHandle) -> bool {
+        self.index(handle)
+            .is_some_and(|index| self.quarantined[index])
+    }
+
+    /// Bodies quarantined since the last call.
+    pub fn drain_quarantined(&mut self) -> impl Iterator<Item = BodyHandle> + '_ {
+        self.quarantine_reports.drain(..)
+    }
+
+    /// Puts a quarantined body back once `set_position` and `set_speed` have
+    /// made it finite again. Returns whether it is simulated now.
+    pub fn release(&mut self, handle: BodyHandle) -> bool {
+        match self.index(handle) {
+            Some(index) if self.is_finite(index) => {
+                self.quarantined[index] = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `None` if either body is gone. The joint is removed along with
+    /// either of its bodies.
+    pub fn add_joint(&mut self, joint: Joint) -> Option<JointHandle> {
+        let a = self.index(joint.a)?;
+        let b = self.index(joint.b)?;
+        let handle = JointHandle(self.next_joint);
+        self.next_joint += 1;
+        self.joints.insert(handle, joint);
+        self.wake_index(a);
+        self.wake_index(b);
+        Some(handle)
+    }
+
+    pub fn remove_joint(&mut self, handle: JointHandle) -> bool {
+        match self.joints.remove(&handle) {
+            Some(joint) => {
+                self.wake(joint.a);
+                self.wake(joint.b);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn joint(&self, handle: JointHandle) -> Option<&Joint> {
+        self.joints.get(&handle)
+    }
+
+    pub fn joints(&self) -> impl Iterator<Item = (JointHandle, &Joint)> + '_ {
+        self.joints.iter().map(|(handle, joint)| (*handle, joint))
+    }
+
+    pub fn drain_events(&mut self) -> impl Iterator<Item = CollisionEvent> + '_ {
+        self.events.drain(..)
+    }
+
+    /// Live objects whose bounding boxes overlap `aabb` and that accept `filter`.
+    pub fn query_aabb(&self, aabb: Rectangle, filter: &CollisionFilter) -> Vec<BodyHandle> {
+        (0..self.shapes.len())
+            .filter(|&id| self.active(id))
+            .filter(|&id| filter.should_collide(&self.filters[id]))
+            .filter(|&id| self.aabbs[id].collides_with(&aabb))
+            .map(|id| self.handle(id))
+            .collect()
+    }
+
+    /// Sweeps `shape` from `from` along `motion` and reports the first solid
+    /// body it would touch. The sweep is sampled in steps no longer than half
+    /// the thinner of the two shapes and refined by bisection, so nothing
+    /// thicker than that is tunnelled through. Each body gets at most
+    /// `MAX_CAST_STEPS` samples, so very long casts can miss thin bodies.
+    /// Sensors are skipped, and so are one-way bodies approached from their
+    /// open side.
+    pub fn cast_shape(
+        &self,
+        shape: &MetaObject,
+        from: Vector,
+        motion: Vector,
+        filter: &CollisionFilter,
+    ) -> Option<ShapeHit> {
+        let extent = shape.aabb();
+        let swept = extent.union(&translate(extent, motion)).expand(1.0);
+        let overlap = |j: usize, t: f64| {
+            shape
+                .at(from + motion * t)
+                .collides_with(&self.placed(j, Vector::zero()))
+                .filter(|vector| vector.len() > 0.00000001)
+        };
+        let mut best: Option<ShapeHit> = None;
+        for handle in self.query_aabb(translate(swept, from), filter) {
+            let j = handle.index;
+            if self.sensors[j] {
                 continue;
             }
-            quad_tree.insert(id, obj.aabb());
+            let thinnest = |size: Vector| size.x.min(size.y);
+            let step = (thinnest(extent.size).min(thinnest(self.aabbs[j].size)) / 2.0).max(0.01);
+            let steps = (motion.len() / step)
+                .ceil()
+                .clamp(1.0, MAX_CAST_STEPS as f64) as usize;
+            let at = |k: usize| k as f64 / steps as f64;
+            let limit = best.map_or(1.0, |hit| hit.fraction);
+            let Some(k) = (0..=steps)
+                .take_while(|&k| at(k.saturating_sub(1)) < limit)
+                .find(|&k| overlap(j, at(k)).is_some())
+            else {
+                continue;
+            };
+            let (mut lo, mut hi) = (at(k.saturating_sub(1)), at(k));
+            for _ in 0..32 {
+                let mid = (lo + hi) / 2.0;
+                if overlap(j, mid).is_some() {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                }
+            }
+            let Some(vector) = overlap(j, hi) else {
+                continue;
+            };
+            let normal = -vector.norm();
+            if lo >= limit
+                || self.one_way[j].is_some_and(|direction| k == 0 || normal.dot(&direction) <= 0.0)
+            {
+                continue;
+            }
+            best = Some(ShapeHit {
+                body: handle,
+                fraction: lo,
+                normal,
+            });
+        }
+        best
+    }
+
+    /// FNV-1a over every slot's generation, position, speed and angle bits. Peers
+    /// running in lockstep can compare it after each tick to detect desync.
+    pub fn state_hash(&self) -> u64 {
+        let mut hash = FNV_OFFSET;
+        for index in 0..self.shapes.len() {
+            hash = fnv(hash, self.alive[index] as u64);
+            hash = fnv(hash, self.generations[index] as u64);
+            if !self.alive[index] {
+                continue;
+            }
+            let angular = Vector::new(self.angles[index], self.angular_speeds[index]);
+            for value in [self.positions[index], self.speeds[index], angular]
+                .iter()
+                .flat_map(|v| [v.x, v.y])
+            {
+                hash = fnv(hash, value.to_bits());
+            }
+        }
+        hash
+    }
+
+    pub fn set_boundary(&mut self, boundary: Boundary) {
+        self.boundary = boundary;
+        self.resting_tree = None;
+    }
+
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    /// Bodies removed by `Boundary::Kill` since the last call.
+    pub fn drain_exited(&mut self) -> impl Iterator<Item = BodyHandle> + '_ {
+        self.exited.drain(..)
+    }
+
+    pub fn set_sleep(&mut self, sleep: SleepConfig) {
+        self.sleep = sleep;
+        if !sleep.enabled {
+            for index in 0..self.shapes.len() {
+                self.wake_index(index);
+            }
+        }
+    }
+
+    pub fn sleep(&self) -> SleepConfig {
+        self.sleep
+    }
+
+    pub fn is_sleeping(&self, handle: BodyHandle) -> bool {
+        self.index(handle).is_some_and(|index| self.sleeping[index])
+    }
+
+    /// Wakes the body and every body sleeping in the same island.
+    pub fn wake(&mut self, handle: BodyHandle) {
+        if let Some(index) = self.index(handle) {
+            self.wake_index(index);
         }
+    }
+
+    /// Acceleration given to every body with a finite mass.
+    pub fn set_gravity(&mut self, gravity: Vector) {
+        self.gravity = gravity;
+    }
+
+    pub fn gravity(&self) -> Vector {
+        self.gravity
+    }
+
+    pub fn set_solver(&mut self, solver: SolverConfig) {
+        self.solver = solver;
+    }
+
+    pub fn solver(&self) -> SolverConfig {
+        self.solver
+    }
+
+    /// Where `StepStats::timings` read the time from.
+    #[cfg(feature = "timings")]
+    pub fn set_clock(&mut self, clock: impl crate::stats::Clock + 'static) {
+        self.clock = Box::new(clock);
+    }
+
+    fn now(&self) -> f64 {
+        #[cfg(feature = "timings")]
+        return self.clock.now();
+        #[cfg(not(feature = "timings"))]
+        return 0.0;
+    }
+
+    fn quad_tree(&self) -> QuadTree {
+        self.build_tree(|_| true)
+    }
+
+    fn build_tree(&self, include: impl Fn(usize) -> bool) -> QuadTree {
+        let bounds = Rectangle::new_vec(Vector::zero(), self.size);
+        let mut quad_tree = if self.boundary == Boundary::Open {
+            QuadTree::new_unbounded(bounds)
+        } else {
+            QuadTree::new(bounds)
+        };
+        for (id, aabb) in self.aabbs.iter().enumerate() {
+            if !self.active(id) || !include(id) {
+                continue;
+            }
+            quad_tree.insert(id, *aabb);
+        }
+        quad_tree
+    }
+
+    pub fn export_quad_tree(&self) -> Box<[f64]> {
+        let quad_tree = self.quad_tree();
         let mut vec = Vec::new();
         quad_tree.export(&mut vec);
         vec.into_boxed_slice()
     }
 
-    pub fn export(&self) -> Box<[f64]> {
-        let mut vec = Vec::with_capacity(self.objects.len() * 4);
+    pub fn export(&self, color: impl Fn(BodyHandle) -> f64) -> Box<[f64]> {
+        let mut vec = Vec::with_capacity(self.shapes.len() * 4);
 
-        for (idx, object) in self.objects.iter().enumerate() {
-            let health = self.health[idx];
-            if health <= 0 {
+        for idx in 0..self.shapes.len() {
+            if !self.active(idx) {
                 continue;
             }
-            match object {
-                MetaObject::Rect(rect) => {
+            let color = color(self.handle(idx));
+            let aabb = self.aabbs[idx];
+            match self.placed(idx, Vector::zero()) {
+                Placed::Rect(_) => {
                     vec.push(1.0);
-                    vec.push(health as f64);
-                    vec.push(rect.left());
-                    vec.push(rect.top());
-                    vec.push(rect.shape.size.x);
-                    vec.push(rect.shape.size.y);
+                    vec.push(color);
+                    vec.push(aabb.left());
+                    vec.push(aabb.top());
+                    vec.push(aabb.size.x);
+                    vec.push(aabb.size.y);
                 }
-                MetaObject::Circle(circle) => {
+                Placed::Circle(circle) => {
                     vec.push(2.0);
-                    vec.push(health as f64);
+                    vec.push(color);
                     vec.push(circle.center.x);
                     vec.push(circle.center.y);
                     vec.push(circle.radius);
                 }
-                MetaObject::Poly(poly) => {
+                Placed::Poly(poly) => {
                     vec.push(3.0);
-                    vec.push(health as f64);
-                    vec.push(poly.points_len() as f64);
+                    vec.push(color);
+                    vec.push(poly.points.len() as f64);
                     for point in poly.points() {
                         vec.push(point.x);
                         vec.push(point.y);
                     }
-                    let aabb = poly.aabb();
                     vec.push(aabb.left());
                     vec.push(aabb.top());
                     vec.push(aabb.size.x);
@@ -151,95 +758,716 @@ impl World {
         vec.into_boxed_slice()
     }
 
-    pub fn tick(&mut self, delta_time: f64) {
-        // return;
-        let mut quad_tree = QuadTree::new(Rectangle::new_vec(Vector::zero(), self.size));
-        for (id, obj) in self.objects.iter().enumerate() {
-            if self.health[id] <= 0 {
-                continue;
+    pub fn tick(&mut self, delta_time: f64) -> StepStats {
+        self.tick_with(delta_time, &mut Bounce)
+    }
+
+    /// Runs one step in three phases: broad and narrow phase only read the
+    /// world and may run in parallel, then the handler sees contacts one by
+    /// one in pair order and the resolved ones go through the solver together,
+    /// so both paths produce exactly the same result.
+    ///
+    /// Pairs are sorted by index rather than by quad tree layout, and a step
+    /// only uses IEEE operations that are correctly rounded everywhere, so the
+    /// same world and inputs give the same `state_hash` on every machine.
+    pub fn tick_with(&mut self, delta_time: f64, handler: &mut dyn ContactHandler) -> StepStats {
+        let mut stats = StepStats::default();
+        let start = self.now();
+
+        self.quarantine_non_finite();
+        let resting_tree = match self.resting_tree.take() {
+            Some(tree) => tree,
+            None => self.build_tree(|id| self.resting(id)),
+        };
+        let quad_tree = self.build_tree(|id| !self.resting(id));
+        stats.tree_depth = quad_tree.depth();
+        stats.tree_nodes = quad_tree.node_count();
+
+        let mut pairs = map_indices(self.shapes.len(), |i| {
+            self.candidates(&quad_tree, &resting_tree, i)
+        })
+        .concat();
+        // A pair touching across a wrapped edge can come up once in place and
+        // once shifted; only the shifted one overlaps, so both are kept.
+        // Offsets are never -0.0, so equal ones sort next to each other.
+        pairs.sort_by(|a, b| {
+            (a.0, a.1)
+                .cmp(&(b.0, b.1))
+                .then(a.2.x.total_cmp(&b.2.x))
+                .then(a.2.y.total_cmp(&b.2.y))
+        });
+        pairs.dedup();
+        stats.candidates = pairs.len();
+        let broad_done = self.now();
+
+        let collisions = map_indices(pairs.len(), |k| {
+            let (i, j, offset) = pairs[k];
+            if !self.aabbs[i].collides_with(&translate(self.aabbs[j], offset)) {
+                return None;
             }
-            quad_tree.insert(id, obj.aabb());
-        }
+            Some(
+                self.placed(i, Vector::zero())
+                    .collides_with(&self.placed(j, offset)),
+            )
+        });
+        stats.narrow_tests = collisions.iter().filter(|it| it.is_some()).count();
+        stats.aabb_rejections = stats.candidates - stats.narrow_tests;
+        let collisions: Vec<_> = collisions.into_iter().map(Option::flatten).collect();
+        stats.contacts = collisions.iter().filter(|it| it.is_some()).count();
+        let narrow_done = self.now();
+        self.resting_tree = Some(resting_tree);
+
+        self.integrate_speeds(delta_time);
+        let mut contacts = BTreeMap::new();
+        let mut resolved = Vec::new();
+        for (&(i, j, _), collision) in pairs.iter().zip(collisions) {
+            let vector = match collision {
+                Some(vector) if self.alive[i] && self.alive[j] => vector,
+                _ => continue,
+            };
 
-        let mut sum = 0.0;
-        for i in 0..self.objects.len() {
-            self.ticked[i] = self.tick;
-            if self.health[i] <= 0 {
+            let vector_norm = vector.try_norm().unwrap_or_else(Vector::zero);
+            let sensor = self.sensors[i] || self.sensors[j];
+            let (a, b) = (self.handle(i), self.handle(j));
+            contacts.insert(
+                (a, b),
+                CollisionEvent {
+                    a,
+                    b,
+                    normal: vector_norm,
+                    depth: vector.len(),
+                    phase: OverlapPhase::Begin,
+                    sensor,
+                },
+            );
+            if sensor || vector.len().abs() <= 0.00000001 {
+                continue;
+            }
+            if self.passes_through(i, j, vector_norm) {
                 continue;
             }
 
-            'inner: for j in quad_tree.might_collide(i, self.objects[i].aabb()) {
-            //'inner: for j in (i+1)..self.objects.len() {
-                if self.ticked[j] == self.tick {
-                    continue;
+            let mut contact = Contact {
+                a,
+                b,
+                normal: vector_norm,
+                depth: vector.len(),
+                destroy_a: false,
+                destroy_b: false,
+            };
+            if handler.on_contact(&mut contact) == Response::Resolve {
+                if self.sleeping[i] != self.sleeping[j] {
+                    self.wake_index(if self.sleeping[i] { i } else { j });
                 }
-                if self.health[j] <= 0  {
-                    continue 'inner;
+                resolved.push(self.solver_contact(i, j, &contact));
+            }
+            if contact.destroy_a {
+                self.remove_index(i);
+            }
+            if contact.destroy_b {
+                self.remove_index(j);
+            }
+        }
+
+        resolved.retain(|it| self.alive[it.a] && self.alive[it.b]);
+        let joints = self.solver_joints();
+        let links: Vec<_> = resolved
+            .iter()
+            .map(|it| (it.a, it.b))
+            .chain(joints.iter().map(|it| (it.a, it.b)))
+            .collect();
+        self.solve(resolved, &joints, delta_time);
+
+        for i in 0..self.shapes.len() {
+            if self.active(i) && !self.resting(i) {
+                self.apply_boundary(i);
+            }
+        }
+
+        // Resting pairs are not tested again, but they still touch.
+        for (pair, event) in self.contacts.iter() {
+            if let (Some(a), Some(b)) = (self.index(pair.0), self.index(pair.1)) {
+                if self.resting(a) && self.resting(b) {
+                    contacts.entry(*pair).or_insert(*event);
                 }
-                sum += 1.0;
-                let a = &self.objects[i];
-                let b = &self.objects[j];
-                if !a.aabb().collides_with(&b.aabb()) {
-                    continue 'inner;
+            }
+        }
+        self.update_contacts(contacts);
+        let contacts = &self.contacts;
+        self.passing.retain(|pair| contacts.contains_key(pair));
+        let response_done = self.now();
+
+        for index in 0..self.shapes.len() {
+            if self.resting(index) {
+                continue;
+            }
+            self.positions[index] += self.speeds[index] * delta_time;
+            if self.angular_speeds[index] != 0.0 {
+                self.angles[index] += self.angular_speeds[index] * delta_time;
+                self.pose(index);
+            }
+            self.update_aabb(index);
+        }
+        self.update_sleep(&links, delta_time);
+        self.quarantine_non_finite();
+
+        let end = self.now();
+        stats.timings.broad = broad_done - start;
+        stats.timings.narrow = narrow_done - broad_done;
+        stats.timings.response = response_done - narrow_done;
+        stats.timings.integrate = end - response_done;
+        stats
+    }
+
+    /// The first half of semi-implicit Euler: speeds take in gravity and the
+    /// accumulated forces before contacts are solved, positions follow after.
+    fn integrate_speeds(&mut self, delta_time: f64) {
+        for index in 0..self.shapes.len() {
+            if self.active(index)
+                && !self.resting(index)
+                && self.body_types[index] == BodyType::Dynamic
+            {
+                let inv_mass = self.inv_masses[index];
+                if inv_mass != 0.0 {
+                    self.speeds[index] +=
+                        (self.gravity + self.forces[index] * inv_mass) * delta_time;
                 }
+                self.angular_speeds[index] +=
+                    self.torques[index] * self.inv_inertias[index] * delta_time;
+                self.speeds[index] =
+                    self.speeds[index] / (1.0 + delta_time * self.linear_damping[index]);
+                self.angular_speeds[index] /= 1.0 + delta_time * self.angular_damping[index];
+            }
+            self.forces[index] = Vector::zero();
+            self.torques[index] = 0.0;
+        }
+    }
+
+    /// Whether a one-way body in the pair lets the other through. That is
+    /// decided when they first touch and kept while they overlap, so a body
+    /// halfway through is not snapped out on the far side.
+    fn passes_through(&mut self, i: usize, j: usize, normal: Vector) -> bool {
+        let pair = (self.handle(i), self.handle(j));
+        if self.passing.contains(&pair) {
+            return true;
+        }
+        if self.contacts.contains_key(&pair) {
+            return false;
+        }
+        let solid = |index: usize, away: Vector| {
+            self.one_way[index].is_none_or(|direction| away.dot(&direction) > 0.0)
+        };
+        if solid(i, normal) && solid(j, -normal) {
+            return false;
+        }
+        self.passing.insert(pair);
+        true
+    }
+
+    fn quarantine_non_finite(&mut self) {
+        for index in 0..self.shapes.len() {
+            if !self.active(index) || self.is_finite(index) {
+                continue;
+            }
+            let handle = self.handle(index);
+            log::warn!("quarantined {:?} at {:?}", handle, self.positions[index]);
+            self.quarantined[index] = true;
+            self.quarantine_reports.push(handle);
+        }
+    }
+
+    fn is_finite(&self, index: usize) -> bool {
+        let position = self.positions[index];
+        let speed = self.speeds[index];
+        position.x.is_finite()
+            && position.y.is_finite()
+            && speed.x.is_finite()
+            && speed.y.is_finite()
+            && self.angles[index].is_finite()
+            && self.angular_speeds[index].is_finite()
+    }
+
+    fn active(&self, index: usize) -> bool {
+        self.alive[index] && !self.quarantined[index]
+    }
+
+    fn resting(&self, index: usize) -> bool {
+        self.sleeping[index] || self.body_types[index] == BodyType::Static
+    }
 
-                let collision = a.collides_with(b);
+    /// A static body that moves has to be put back in the resting tree.
+    fn moved(&mut self, index: usize) {
+        if self.resting(index) {
+            self.wake_touching(index);
+        }
+        if self.body_types[index] == BodyType::Static {
+            self.resting_tree = None;
+        }
+        self.wake_index(index);
+    }
 
-                if let Some(vector) = collision {
-                    let vector_norm = vector.norm();
-                    if vector.len().abs() > 0.00000001 {
-                        let a_collision_speed = vector_norm.dot(&a.speed());
-                        let b_collision_speed = vector_norm.dot(&b.speed());
+    /// Wakes everything in contact with `index`, so pairs that rest on it are
+    /// tested again instead of being carried over to the next tick.
+    fn wake_touching(&mut self, index: usize) {
+        let handle = self.handle(index);
+        let touching: Vec<_> = (self.contacts.keys())
+            .filter_map(|&(a, b)| match (a == handle, b == handle) {
+                (true, _) => self.index(b),
+                (_, true) => self.index(a),
+                _ => None,
+            })
+            .collect();
+        for other in touching {
+            self.wake_index(other);
+        }
+    }
 
-                        let a_kick = vector_norm * (-2.0 * a_collision_speed);
-                        let b_kick = vector_norm * (-2.0 * b_collision_speed);
+    fn wake_index(&mut self, index: usize) {
+        self.sleep_timers[index] = 0.0;
+        if !self.sleeping[index] {
+            return;
+        }
+        let island = self.islands[index];
+        for other in 0..self.shapes.len() {
+            if self.sleeping[other] && self.islands[other] == island {
+                self.sleeping[other] = false;
+                self.sleep_timers[other] = 0.0;
+            }
+        }
+        self.resting_tree = None;
+    }
+
+    /// Dynamic bodies joined by resolved contacts form islands. An island
+    /// whose bodies have all been slow for `SleepConfig::time` goes to sleep
+    /// as a whole, and waking any of its bodies later wakes all of them.
+    fn update_sleep(&mut self, links: &[(usize, usize)], delta_time: f64) {
+        if !self.sleep.enabled {
+            return;
+        }
+        let awake = |world: &Self, index: usize| {
+            world.active(index)
+                && !world.sleeping[index]
+                && world.body_types[index] == BodyType::Dynamic
+        };
+        let links: Vec<_> = links
+            .iter()
+            .copied()
+            .filter(|&(a, b)| awake(self, a) && awake(self, b))
+            .collect();
+        let islands = island::islands(self.shapes.len(), &links);
+
+        let mut ready = vec![true; self.shapes.len()];
+        for index in 0..self.shapes.len() {
+            if !awake(self, index) {
+                continue;
+            }
+            let slow = self.speeds[index].len() < self.sleep.linear_speed
+                && self.angular_speeds[index].abs() < self.sleep.angular_speed;
+            self.sleep_timers[index] = if slow {
+                self.sleep_timers[index] + delta_time
+            } else {
+                0.0
+            };
+            ready[islands[index]] &= self.sleep_timers[index] >= self.sleep.time;
+        }
+        for index in 0..self.shapes.len() {
+            if awake(self, index) && ready[islands[index]] {
+                self.sleeping[index] = true;
+                self.islands[index] = islands[index];
+                self.speeds[index] = Vector::zero();
+                self.angular_speeds[index] = 0.0;
+                self.resting_tree = None;
+            }
+        }
+    }
 
-                        self.objects[i].kick(a_kick);
-                        self.objects[i].mov(vector * -0.5);
+    /// Resting bodies, static or asleep, sit in a tree of their own that is
+    /// only rebuilt when one of them changes. They never look for pairs in
+    /// place, so two of them are never tested; the moving side of a pair
+    /// finds them instead.
+    ///
+    /// When the world wraps, a body sticking over an edge also looks for
+    /// pairs from the opposite side, so a pair touching across the edge is
+    /// found by whichever of its bodies sticks out, resting or not. Each pair
+    /// carries the offset to add to its second body's position, which is zero
+    /// unless it touches across an edge.
+    fn candidates(
+        &self,
+        moving: &QuadTree,
+        resting: &QuadTree,
+        i: usize,
+    ) -> Vec<(usize, usize, Vector)> {
+        if !self.active(i) {
+            return Vec::new();
+        }
+        let resting_i = self.resting(i);
+        let mut shifts = Vec::new();
+        if !resting_i {
+            shifts.push(Vector::zero());
+        }
+        if self.boundary == Boundary::Wrap {
+            shifts.extend(boundary::wrap_shifts(self.aabbs[i], self.size));
+        }
 
-                        self.objects[j].kick(b_kick);
-                        self.objects[j].mov(vector * 0.5);
+        let mut pairs = Vec::new();
+        for shift in shifts {
+            let aabb = translate(self.aabbs[i], shift);
+            let found = moving
+                .might_collide(i, aabb)
+                .filter(|&j| j > i || shift != Vector::zero())
+                .chain(
+                    (!resting_i)
+                        .then(|| resting.might_collide(i, aabb))
+                        .into_iter()
+                        .flatten(),
+                )
+                .filter(|&j| self.active(j))
+                .filter(|&j| self.filters[i].should_collide(&self.filters[j]))
+                .map(|j| {
+                    if i < j {
+                        (i, j, Vector::zero() - shift)
+                    } else {
+                        (j, i, shift)
+                    }
+                });
+            pairs.extend(found);
+        }
+        pairs.sort_by_key(|&(i, j, _)| (i, j));
+        pairs
+    }
 
-                        self.health[i] -= 1;
-                        self.health[j] -= 1;
+    fn apply_boundary(&mut self, i: usize) {
+        match self.boundary {
+            Boundary::Walls { restitution } if self.body_types[i] == BodyType::Dynamic => {
+                self.bounce(i, restitution)
+            }
+            Boundary::Wrap => {
+                let position = &mut self.positions[i];
+                for (value, size) in [
+                    (&mut position.x, self.size.x),
+                    (&mut position.y, self.size.y),
+                ] {
+                    if *value < 0.0 {
+                        *value += size;
+                    } else if *value >= size {
+                        *value -= size;
                     }
                 }
+                self.update_aabb(i);
+            }
+            Boundary::Kill => {
+                let bounds = Rectangle::new_vec(Vector::zero(), self.size);
+                if !self.aabbs[i].collides_with(&bounds) {
+                    self.exited.push(self.handle(i));
+                    self.remove_index(i);
+                }
             }
+            _ => {}
+        }
+    }
+
+    /// Pushes the body back inside and turns it around if it was heading
+    /// out.
+    fn bounce(&mut self, i: usize, restitution: f64) {
+        let aabb = self.aabbs[i];
+        let speed = self.speeds[i];
+        let kick_factor = -(1.0 + restitution);
+        let mut kick = Vector::zero();
+        let mut mov = Vector::zero();
+        if aabb.left() < 0.0 {
+            kick.x = speed.x.min(0.0) * kick_factor;
+            mov.x = -aabb.left();
+        }
+        if aabb.right() > self.size.x {
+            kick.x = speed.x.max(0.0) * kick_factor;
+            mov.x = self.size.x - aabb.right();
+        }
+        if aabb.top() < 0.0 {
+            kick.y = speed.y.min(0.0) * kick_factor;
+            mov.y = -aabb.top();
+        }
+        if aabb.bottom() > self.size.y {
+            kick.y = speed.y.max(0.0) * kick_factor;
+            mov.y = self.size.y - aabb.bottom();
+        }
 
-            let aabb = self.objects[i].aabb();
-            let speed = self.objects[i].speed();
-            let mut kick = Vector::zero();
-            let mut mov = Vector::zero();
-            if aabb.left() < 0.0 {
-                kick.x = speed.x * -2.0;
-                mov.x = -aabb.left();
+        self.speeds[i] += kick;
+        self.positions[i] += mov;
+        self.update_aabb(i);
+    }
+
+    fn handle(&self, index: usize) -> BodyHandle {
+        BodyHandle {
+            index,
+            generation: self.generations[index],
+        }
+    }
+
+    fn index(&self, handle: BodyHandle) -> Option<usize> {
+        let alive = handle.index < self.shapes.len()
+            && self.alive[handle.index]
+            && self.generations[handle.index] == handle.generation;
+        alive.then_some(handle.index)
+    }
+
+    fn remove_index(&mut self, index: usize) {
+        if !self.alive[index] {
+            return;
+        }
+        if self.resting(index) {
+            self.wake_touching(index);
+            self.wake_index(index);
+            self.resting_tree = None;
+        }
+        let handle = self.handle(index);
+        self.joints
+            .retain(|_, joint| joint.a != handle && joint.b != handle);
+        self.alive[index] = false;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.free.push(index);
+    }
+
+    fn update_aabb(&mut self, index: usize) {
+        self.aabbs[index] = translate(self.local_aabbs[index], self.positions[index]);
+    }
+
+    /// Turns the shape to the body's angle.
+    fn pose(&mut self, index: usize) {
+        let rot = self.rotation(index);
+        let start = self.point_spans[index].start;
+        let len = self.outline_len(index);
+        let outline = &mut self.points[start..start + len];
+        match &self.shapes[index] {
+            MetaObject::Poly(poly) => {
+                for (point, local) in outline.iter_mut().zip(poly.points()) {
+                    *point = local.rotate(rot);
+                }
             }
-            if aabb.right() > self.size.x {
-                kick.x = speed.x * -2.0;
-                mov.x = self.size.x - aabb.right();
+            MetaObject::Rect(rect) => {
+                for (point, local) in outline.iter_mut().zip(rect.aabb().points()) {
+                    *point = local.rotate(rot);
+                }
             }
-            if aabb.top() < 0.0 {
-                kick.y = speed.y * -2.0;
-                mov.y = -aabb.top();
+            MetaObject::Circle(_) => {}
+        }
+        self.local_aabbs[index] =
+            polyhedron::bounds(outline).unwrap_or_else(|| self.shapes[index].aabb());
+        self.update_aabb(index);
+    }
+
+    /// How much of the body's span of `points` its turned outline uses: all of
+    /// a polygon, the corners of a turned rectangle and nothing otherwise.
+    fn outline_len(&self, index: usize) -> usize {
+        match &self.shapes[index] {
+            MetaObject::Poly(poly) => poly.points().len(),
+            MetaObject::Rect(_) if self.angles[index] != 0.0 => 4,
+            _ => 0,
+        }
+    }
+
+    /// The body's shape as the narrow phase sees it: turned, in place and
+    /// shifted by `offset` for pairs that touch across a wrapped edge. A
+    /// turned rectangle is tested as a polygon.
+    fn placed(&self, index: usize, offset: Vector) -> Placed<'_> {
+        let position = self.positions[index] + offset;
+        match self.outline_len(index) {
+            0 => self.shapes[index].at(position),
+            len => {
+                let start = self.point_spans[index].start;
+                Placed::Poly(Polygon::new(position, &self.points[start..start + len]))
             }
-            if aabb.bottom() > self.size.y {
-                kick.y = speed.y * -2.0;
-                mov.y = self.size.y - aabb.bottom();
+        }
+    }
+
+    /// Makes sure the body's span of `points` fits its outline at any angle.
+    /// A span that is too small moves to the end of the buffer, and the
+    /// buffer is packed again once more than half of it is left behind by
+    /// such moves.
+    fn reserve_points(&mut self, index: usize) {
+        let needed = match &self.shapes[index] {
+            MetaObject::Poly(poly) => poly.points().len(),
+            MetaObject::Rect(_) => 4,
+            MetaObject::Circle(_) => 0,
+        };
+        if self.point_spans[index].len() >= needed {
+            return;
+        }
+        self.point_spans[index] = self.points.len()..self.points.len() + needed;
+        self.points
+            .resize(self.points.len() + needed, Vector::zero());
+
+        let used: usize = self.point_spans.iter().map(|span| span.len()).sum();
+        if self.points.len() > 2 * used {
+            let mut points = Vec::with_capacity(used);
+            for span in self.point_spans.iter_mut() {
+                let start = points.len();
+                points.extend_from_slice(&self.points[span.clone()]);
+                *span = start..points.len();
             }
+            self.points = points;
+        }
+    }
 
-            self.objects[i].kick(kick);
-            self.objects[i].mov(mov);
+    fn rotation(&self, index: usize) -> Rot {
+        let (sin, cos) = sin_cos(self.angles[index]);
+        Rot { cos, sin }
+    }
+
+    fn update_inv_mass(&mut self, index: usize) {
+        let inertia = self.shapes[index].unit_inertia();
+        let inv_mass = match self.body_types[index] {
+            BodyType::Dynamic => 1.0 / self.masses[index],
+            _ => 0.0,
+        };
+        self.inv_masses[index] = inv_mass;
+        self.inv_inertias[index] = if inertia > 0.0 {
+            inv_mass / inertia
+        } else {
+            0.0
+        };
+    }
+
+    fn solver_contact(&self, a: usize, b: usize, contact: &Contact) -> SolverContact {
+        let (ma, mb) = (self.materials[a], self.materials[b]);
+        let (normal_impulse, tangent_impulse) = self
+            .impulses
+            .get(&(contact.a, contact.b))
+            .copied()
+            .unwrap_or((0.0, 0.0));
+        SolverContact {
+            a,
+            b,
+            normal: contact.normal,
+            depth: contact.depth,
+            static_friction: (ma.static_friction * mb.static_friction).sqrt(),
+            dynamic_friction: (ma.dynamic_friction * mb.dynamic_friction).sqrt(),
+            restitution: ma.restitution.max(mb.restitution),
+            normal_impulse,
+            tangent_impulse,
         }
+    }
 
-        log::debug!("{}", sum / self.objects.len() as f64);
+    /// Joints whose bodies are not both resting, in world space. A moving
+    /// body wakes whatever sleeps on the other end.
+    fn solver_joints(&mut self) -> Vec<SolverJoint> {
+        let joints: Vec<_> = self
+            .joints
+            .values()
+            .filter_map(|joint| Some((self.index(joint.a)?, self.index(joint.b)?, *joint)))
+            .filter(|&(a, b, _)| self.active(a) && self.active(b))
+            .filter(|&(a, b, _)| !(self.resting(a) && self.resting(b)))
+            .collect();
 
-        for (idx, obj) in self.objects.iter_mut().enumerate() {
-            if self.health[idx] <= 0 {
-                continue;
+        let mut solver_joints = Vec::with_capacity(joints.len());
+        for (a, b, joint) in joints {
+            if self.sleeping[a] || self.sleeping[b] {
+                self.wake_index(if self.sleeping[a] { a } else { b });
+            }
+            let (rot_a, rot_b) = (self.rotation(a), self.rotation(b));
+            let offset_a = joint.anchor_a.rotate(rot_a);
+            let offset_b = joint.anchor_b.rotate(rot_b);
+            let kind = match joint.kind {
+                JointKind::Prismatic { axis, angle } => JointKind::Prismatic {
+                    axis: axis
+                        .rotate(rot_a)
+                        .try_norm()
+                        .unwrap_or_else(|| Vector::new(1.0, 0.0)),
+                    angle,
+                },
+                kind => kind,
+            };
+            solver_joints.push(SolverJoint {
+                a,
+                b,
+                offset_a,
+                offset_b,
+                separation: (self.positions[b] + offset_b) - (self.positions[a] + offset_a),
+                angle: self.angles[b] - self.angles[a],
+                kind,
+            });
+        }
+        solver_joints
+    }
+
+    /// Solves all resolved contacts at once and keeps their impulses, keyed by
+    /// handle pair, to warm start the same contacts next tick.
+    fn solve(&mut self, mut contacts: Vec<SolverContact>, joints: &[SolverJoint], delta_time: f64) {
+        let bodies = Bodies {
+            positions: &mut self.positions,
+            speeds: &mut self.speeds,
+            angular_speeds: &mut self.angular_speeds,
+            inv_masses: &self.inv_masses,
+            inv_inertias: &self.inv_inertias,
+        };
+        solver::solve(&mut contacts, joints, bodies, &self.solver, delta_time);
+        self.impulses.clear();
+        for contact in contacts.iter() {
+            let pair = (self.handle(contact.a), self.handle(contact.b));
+            self.impulses
+                .insert(pair, (contact.normal_impulse, contact.tangent_impulse));
+            self.update_aabb(contact.a);
+            self.update_aabb(contact.b);
+        }
+    }
+
+    fn update_contacts(
+        &mut self,
+        mut contacts: BTreeMap<(BodyHandle, BodyHandle), CollisionEvent>,
+    ) {
+        for (pair, contact) in self.contacts.iter() {
+            if !contacts.contains_key(pair) {
+                self.events.push(CollisionEvent {
+                    phase: OverlapPhase::End,
+                    ..*contact
+                });
+            }
+        }
+        for (pair, contact) in contacts.iter_mut() {
+            if self.contacts.contains_key(pair) {
+                contact.phase = OverlapPhase::Stay;
             }
-            obj.tick(delta_time);
+            self.events.push(*contact);
         }
+        self.contacts = contacts;
+    }
+}
+
+/// Most samples `World::cast_shape` takes against one body.
+const MAX_CAST_STEPS: usize = 4096;
 
-        self.tick = !self.tick;
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+fn fnv(mut hash: u64, value: u64) -> u64 {
+    for byte in value.to_le_bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
     }
+    hash
+}
+
+#[cfg(all(feature = "timings", not(target_arch = "wasm32")))]
+fn default_clock() -> Box<dyn crate::stats::Clock> {
+    Box::new(crate::stats::StdClock::default())
+}
+
+/// The browser has no `Instant`; the front-end sets `performance.now()`.
+#[cfg(all(feature = "timings", target_arch = "wasm32"))]
+fn default_clock() -> Box<dyn crate::stats::Clock> {
+    Box::new(|| 0.0)
+}
+
+fn translate(aabb: Rectangle, offset: Vector) -> Rectangle {
+    Rectangle::new_vec(aabb.coord + offset, aabb.size)
+}
+
+#[cfg(feature = "parallel")]
+fn map_indices<R: Send>(len: usize, f: impl Fn(usize) -> R + Sync + Send) -> Vec<R> {
+    use rayon::prelude::*;
+    (0..len).into_par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn map_indices<R>(len: usize, f: impl Fn(usize) -> R) -> Vec<R> {
+    (0..len).map(f).collect()
 }
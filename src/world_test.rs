@@ -0,0 +1,802 @@
+use crate::{
+    body::{BodyHandle, BodyType, Material},
+    boundary::Boundary,
+    circle::CircleObject,
+    events::OverlapPhase,
+    filter::CollisionFilter,
+    geometry::{Rectangle, Rot, Vector},
+    object::MetaObject,
+    polyhedron::PolyhedronObject,
+    rectangle::RectangleObject,
+    response::{Contact, HealthPolicy, Response},
+    world::World,
+};
+
+/// Adds a static body, as level geometry.
+pub(crate) fn add_static(world: &mut World, shape: MetaObject, position: Vector) -> BodyHandle {
+    let body = world.add_body(shape, position, Vector::zero());
+    world.set_body_type(body, BodyType::Static);
+    body
+}
+
+/// A static floor whose top is at y = 890 and `count` boxes of 20 above it,
+/// `spacing` apart with the lowest centred at `first`. Nothing bounces.
+fn stack_boxes(
+    world: &mut World,
+    count: usize,
+    first: f64,
+    spacing: f64,
+) -> (BodyHandle, Vec<BodyHandle>) {
+    let floor = add_static(
+        world,
+        RectangleObject::new_obj(200.0, 20.0),
+        Vector::new(500.0, 900.0),
+    );
+    let material = Material::new(0.5, 0.5).with_restitution(0.0);
+    world.set_material(floor, material);
+    let boxes = (0..count)
+        .map(|k| {
+            let body = world.add_body(
+                RectangleObject::new_obj(20.0, 20.0),
+                Vector::new(500.0, first - k as f64 * spacing),
+                Vector::zero(),
+            );
+            world.set_material(body, material);
+            body
+        })
+        .collect();
+    (floor, boxes)
+}
+
+#[test]
+fn test_sensor_reports_overlap_phases() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let zone = world.add_body(
+        CircleObject::new_obj(20.0),
+        Vector::new(100.0, 100.0),
+        Vector::zero(),
+    );
+    let ball = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(100.0, 100.0),
+        Vector::new(200.0, 0.0),
+    );
+    world.set_sensor(zone, true);
+
+    let mut phases = Vec::new();
+    for _ in 0..4 {
+        world.tick(0.1);
+        for event in world.drain_events() {
+            assert_eq!((event.a, event.b), (zone, ball));
+            assert!(event.sensor);
+            phases.push(event.phase);
+        }
+    }
+
+    assert_eq!(
+        phases,
+        vec![OverlapPhase::Begin, OverlapPhase::Stay, OverlapPhase::End]
+    );
+}
+
+#[test]
+fn test_contact_events_carry_normal_and_depth() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let a = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(100.0, 100.0),
+        Vector::zero(),
+    );
+    let b = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(115.0, 100.0),
+        Vector::new(100.0, 0.0),
+    );
+
+    world.tick(0.1);
+    let events: Vec<_> = world.drain_events().collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!((events[0].a, events[0].b), (a, b));
+    assert_eq!(events[0].phase, OverlapPhase::Begin);
+    assert!(!events[0].sensor);
+    assert!((events[0].normal - Vector::new(1.0, 0.0)).len() < 0.0000001);
+    assert!((events[0].depth - 5.0).abs() < 0.0000001);
+
+    world.tick(0.1);
+    let events: Vec<_> = world.drain_events().collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].phase, OverlapPhase::End);
+}
+
+#[test]
+fn test_ignored_contacts_are_not_pushed_apart() {
+    let mut world = World::empty(1000.0, 1000.0);
+    world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(100.0, 100.0),
+        Vector::zero(),
+    );
+    world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(115.0, 100.0),
+        Vector::zero(),
+    );
+
+    let mut calls = 0;
+    let mut ignore = |_: &mut Contact| {
+        calls += 1;
+        Response::Ignore
+    };
+    world.tick_with(0.1, &mut ignore);
+    world.tick_with(0.1, &mut ignore);
+    assert_eq!(calls, 2);
+
+    let phases: Vec<_> = world.drain_events().map(|it| it.phase).collect();
+    assert_eq!(phases, vec![OverlapPhase::Begin, OverlapPhase::Stay]);
+}
+
+#[test]
+fn test_filtered_bodies_pass_through_each_other() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let a = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(100.0, 100.0),
+        Vector::zero(),
+    );
+    let b = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(115.0, 100.0),
+        Vector::zero(),
+    );
+    world.set_filter(a, CollisionFilter::new(0b01, 0b01));
+    world.set_filter(b, CollisionFilter::new(0b10, 0b10));
+
+    let mut calls = 0;
+    world.tick_with(0.1, &mut |_: &mut Contact| {
+        calls += 1;
+        Response::Resolve
+    });
+    assert_eq!(calls, 0);
+    assert_eq!(world.drain_events().count(), 0);
+    assert_eq!(world.position(a).unwrap(), Vector::new(100.0, 100.0));
+    assert_eq!(world.position(b).unwrap(), Vector::new(115.0, 100.0));
+
+    let area = Rectangle::new(0.0, 0.0, 200.0, 200.0);
+    let probe = CollisionFilter::new(0b01, 0b01);
+    assert_eq!(world.query_aabb(area, &probe), vec![a]);
+    let shape = CircleObject::new_obj(5.0);
+    let from = Vector::new(150.0, 100.0);
+    let hit = world.cast_shape(&shape, from, Vector::new(-100.0, 0.0), &probe);
+    assert_eq!(hit.map(|hit| hit.body), Some(a));
+    let probe = CollisionFilter::new(0b100, 0b100);
+    assert!(world.query_aabb(area, &probe).is_empty());
+    assert!(world
+        .cast_shape(&shape, from, Vector::new(-100.0, 0.0), &probe)
+        .is_none());
+}
+
+#[test]
+fn test_long_casts_stay_bounded() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let wall = add_static(
+        &mut world,
+        RectangleObject::new_obj(1e6, 100.0),
+        Vector::new(500.0, 500.0),
+    );
+    let shape = CircleObject::new_obj(0.01);
+    let from = Vector::new(-1e9, 500.0);
+    let hit = world.cast_shape(
+        &shape,
+        from,
+        Vector::new(2e9, 0.0),
+        &CollisionFilter::default(),
+    );
+    assert_eq!(hit.map(|hit| hit.body), Some(wall));
+}
+
+#[test]
+fn test_health_policy_destroys_bodies() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let a = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(100.0, 100.0),
+        Vector::zero(),
+    );
+    let b = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(115.0, 100.0),
+        Vector::zero(),
+    );
+
+    let mut health = HealthPolicy::new(1);
+    world.tick_with(0.1, &mut health);
+
+    assert_eq!(health.health(a), 0);
+    assert!(!world.is_alive(a));
+    assert!(!world.is_alive(b));
+}
+
+#[test]
+fn test_removed_slots_are_reused_with_new_generation() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let a = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(100.0, 100.0),
+        Vector::zero(),
+    );
+    let b = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(300.0, 100.0),
+        Vector::zero(),
+    );
+
+    assert!(world.remove_body(a));
+    assert!(!world.remove_body(a));
+    assert!(!world.is_alive(a));
+
+    let c = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(500.0, 100.0),
+        Vector::zero(),
+    );
+    assert_eq!(c.index, a.index);
+    assert_ne!(c.generation, a.generation);
+    assert!(world.shape(a).is_none());
+    assert_eq!(world.bodies().collect::<Vec<_>>(), vec![c, b]);
+}
+
+#[test]
+fn test_tick_integrates_positions_and_aabbs() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let body = world.add_body(
+        RectangleObject::new_obj(20.0, 10.0),
+        Vector::new(100.0, 100.0),
+        Vector::new(10.0, -4.0),
+    );
+
+    world.tick(0.5);
+
+    let position = world.position(body).unwrap();
+    assert!((position - Vector::new(105.0, 98.0)).len() < 0.0000001);
+    let aabb = world.aabb(body).unwrap();
+    assert!((aabb.coord - Vector::new(95.0, 93.0)).len() < 0.0000001);
+    assert!((aabb.size - Vector::new(20.0, 10.0)).len() < 0.0000001);
+}
+
+#[test]
+fn test_non_finite_bodies_are_quarantined() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let broken = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(100.0, 100.0),
+        Vector::zero(),
+    );
+    let other = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(105.0, 100.0),
+        Vector::zero(),
+    );
+    world.set_speed(broken, Vector::new(f64::NAN, 0.0));
+
+    world.tick(0.1);
+    assert_eq!(world.drain_quarantined().collect::<Vec<_>>(), vec![broken]);
+    assert!(world.is_quarantined(broken));
+    assert!(!world.is_quarantined(other));
+    let position = world.position(other).unwrap();
+    assert!(position.x.is_finite() && position.y.is_finite());
+    let everywhere = Rectangle::new(0.0, 0.0, 1000.0, 1000.0);
+    let found = world.query_aabb(everywhere, &CollisionFilter::default());
+    assert_eq!(found, vec![other]);
+    assert_eq!(world.export(|_| 0.0).len(), 5);
+
+    assert!(!world.release(broken));
+    world.set_speed(broken, Vector::zero());
+    world.set_position(broken, Vector::new(500.0, 500.0));
+    assert!(world.release(broken));
+    world.tick(0.1);
+    assert_eq!(world.drain_quarantined().count(), 0);
+}
+
+#[test]
+fn test_step_stats_count_each_phase() {
+    let mut world = World::empty(1000.0, 1000.0);
+    for position in [
+        (100.0, 100.0),
+        (110.0, 100.0),
+        (500.0, 500.0),
+        (900.0, 900.0),
+    ] {
+        world.add_body(
+            CircleObject::new_obj(10.0),
+            Vector::new(position.0, position.1),
+            Vector::zero(),
+        );
+    }
+
+    let stats = world.tick(0.0);
+    assert_eq!(stats.contacts, 1);
+    assert!(stats.narrow_tests >= stats.contacts);
+    assert_eq!(stats.candidates, stats.narrow_tests + stats.aabb_rejections);
+    assert!(stats.tree_depth >= 1);
+    assert_eq!((stats.tree_nodes - 1) % 4, 0);
+}
+
+#[test]
+fn test_friction_slows_sliding_contacts() {
+    let run = |material: Material| {
+        let mut world = World::empty(1000.0, 1000.0);
+        let a = world.add_body(
+            CircleObject::new_obj(10.0),
+            Vector::new(100.0, 100.0),
+            Vector::new(50.0, 10.0),
+        );
+        let b = world.add_body(
+            CircleObject::new_obj(10.0),
+            Vector::new(100.0, 115.0),
+            Vector::zero(),
+        );
+        world.set_material(a, material);
+        world.set_material(b, material);
+        world.tick(0.0);
+        (world.speed(a).unwrap(), world.speed(b).unwrap())
+    };
+
+    let (a, b) = run(Material::default());
+    assert_eq!((a.x, b.x), (50.0, 0.0));
+
+    let (a, b) = run(Material::new(1.0, 0.5));
+    assert!((a.x - 45.0).abs() < 1e-9, "{:?}", a);
+    assert!((b.x - 5.0).abs() < 1e-9, "{:?}", b);
+
+    let (a, b) = run(Material::new(10.0, 10.0));
+    assert!((a.x - b.x).abs() < 1e-9, "{:?} {:?}", a, b);
+}
+
+#[test]
+fn test_boxes_stack_under_gravity() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let (floor, boxes) = stack_boxes(&mut world, 3, 860.0, 25.0);
+
+    world.set_gravity(Vector::new(0.0, 200.0));
+    for _ in 0..300 {
+        world.tick(1.0 / 60.0);
+    }
+
+    assert_eq!(world.position(floor), Some(Vector::new(500.0, 900.0)));
+    let mut below = 890.0;
+    for &body in boxes.iter() {
+        let position = world.position(body).unwrap();
+        assert!((position.x - 500.0).abs() < 1e-6, "{:?}", position);
+        assert!((below - 10.0 - position.y).abs() < 1.0, "{:?}", position);
+        assert!(world.speed(body).unwrap().len() < 5.0);
+        below = position.y - 10.0;
+    }
+}
+
+#[test]
+fn test_forces_and_gravity_accelerate_bodies() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let falling = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(100.0, 100.0),
+        Vector::zero(),
+    );
+    let pushed = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(500.0, 100.0),
+        Vector::zero(),
+    );
+    world.set_mass(pushed, 2.0);
+    for mass in [0.0, -1.0, f64::NAN] {
+        world.set_mass(pushed, mass);
+    }
+    assert_eq!(world.mass(pushed), Some(2.0));
+    world.set_gravity(Vector::new(0.0, 10.0));
+
+    world.apply_force(pushed, Vector::new(4.0, -20.0));
+    world.apply_impulse(pushed, Vector::new(2.0, 0.0));
+    world.tick(1.0);
+    assert_eq!(world.speed(falling), Some(Vector::new(0.0, 10.0)));
+    assert_eq!(world.position(falling), Some(Vector::new(100.0, 110.0)));
+    assert_eq!(world.speed(pushed), Some(Vector::new(3.0, 0.0)));
+
+    world.tick(1.0);
+    assert_eq!(world.speed(pushed), Some(Vector::new(3.0, 10.0)));
+
+    world.set_gravity(Vector::zero());
+    world.set_damping(falling, 1.0, 0.0);
+    world.tick(1.0);
+    assert_eq!(world.speed(falling), Some(Vector::new(0.0, 10.0)));
+}
+
+#[test]
+fn test_polygons_of_any_size_collide() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let small = world.add_body(
+        PolyhedronObject::new_obj(10.0, 3),
+        Vector::new(100.0, 100.0),
+        Vector::zero(),
+    );
+    world.add_body(
+        PolyhedronObject::new_obj(20.0, 40),
+        Vector::new(300.0, 300.0),
+        Vector::zero(),
+    );
+    world.remove_body(small);
+    let big = world.add_body(
+        PolyhedronObject::new_obj(20.0, 64),
+        Vector::new(335.0, 300.0),
+        Vector::zero(),
+    );
+    assert_eq!(big.index, small.index);
+    world.set_angle(big, 0.3);
+    world.tick(0.0);
+    assert_eq!(world.drain_events().count(), 1);
+
+    let empty = PolyhedronObject::<f64>::new(10.0, 0);
+    assert!(empty.rotated(Rot::from_angle(1.0)).points().is_empty());
+}
+
+#[test]
+fn test_torque_turns_bodies() {
+    let mut world = World::empty(1000.0, 1000.0);
+    let wheel = world.add_body(
+        CircleObject::new_obj(10.0),
+        Vector::new(100.0, 100.0),
+        Vector::zero(),
+    );
+    let square = world.add_body(
+        RectangleObject::new_obj(20.0, 10.0),
+        Vector::new(300.0, 100.0),
+        Vector::zero(),
+    );
+    let triangle = world.add_body(
+        PolyhedronObject::new_obj(10.0, 3),
+        Vector::new(500.0, 100.0),
+        Vector::zero(),
+    );
+
+    world.apply_torque(wheel, 200.0);
+    world.tick(0.5);
+    assert_eq!(world.angular_speed(wheel), Some(2.0));
+    assert_eq!(world.angle(wheel), Some(1.0));
+
+    let before = world.aabb(triangle).unwrap();
+    world.set_angular_speed(square, 1.0);
+    world.set_angle(triangle, std::f64::consts::PI);
+    world.tick(0.5);
+    assert_eq!(world.angle(square), Some(0.5));
+    let (sin, cos) = 0.5_f64.sin_cos();
+    let turned = Vector::new(20.0 * cos + 10.0 * sin, 20.0 * sin + 10.0 * cos);
+    assert!((world.aabb(square).unwrap().size - turned).len() < 1e-9);
+    let after = world.aabb(triangle).unwrap();
+    assert!((after.top() - (200.0 - before.bottom())).abs() < 1e-9);
+    assert!((after.bottom() - (200.0 - before.top())).abs() < 1e-9);
+
+    // Standing upright, the square only reaches the dot below it.
+    world.set_angle(square, std::f64::consts::FRAC_PI_2);
+    let side = world.add_body(
+        CircleObject::new_obj(1.0),
+        Vector::new(308.0, 100.0),
+        Vector::zero(),
+    );
+    let below = world.add_body(
+        CircleObject::new_obj(1.0),
+        Vector::new(300.0, 108.0),
+        Vector::zero(),
+    );
+    world.drain_events().count();
+    world.tick(0.0);
+    let touched: Vec<_> = world.drain_events().flat_map(|it| [it.a, it.b]).collect();
+    assert!(!touched.contains(&side));
+    assert!(touched.contains(&below));
+}
+
+#[test]
+fn test_kinematic_bodies_push_without_being_pushed() {
+    let mut world = World::empty(1000.0, 1000.0);
+    world.set_gravity(Vector::new(0.0, 100.0));
+    let wall = add_static(
+        &mut world,
+        RectangleObject::new_obj(20.0, 200.0),
+        Vector::new(100.0, 500.0),
+    );
+    add_static(
+        &mut world,
+        RectangleObject::new_obj(20.0, 200.0),
+        Vector::new(115.0, 500.0),
+    );
+    let platform = world.add_body(
+        RectangleObject::new_obj(100.0, 10.0),
+        Vector::new(500.0, 500.0),
+        Vector::new(0.0, -10.0),
+    );
+    let crate_ = world.add_body(
+        RectangleObject::new_obj(10.0, 10.0),
+        Vector::new(500.0, 491.0),
+        Vector::zero(),
+    );
+    world.set_body_type(platform, BodyType::Kinematic);
+
+    let stats = world.tick(0.1);
+    assert_eq!(stats.contacts, 1);
+    assert_eq!(world.position(wall), Some(Vector::new(100.0, 500.0)));
+    assert_eq!(world.speed(platform), Some(Vector::new(0.0, -10.0)));
+    assert!((world.position(platform).unwrap().y - 499.0).abs() < 1e-9);
+    assert!(world.speed(crate_).unwrap().y < 0.0);
+}
+
+#[test]
+fn test_resting_islands_sleep_and_wake_together() {
+    let mut world = World::empty(1000.0, 1000.0);
+    world.set_gravity(Vector::new(0.0, 200.0));
+    let (floor, boxes) = stack_boxes(&mut world, 2, 880.0, 20.0);
+    let material = world.material(floor).unwrap();
+
+    for _ in 0..120 {
+        world.tick(1.0 / 60.0);
+    }
+    assert!(boxes.iter().all(|&body| world.is_sleeping(body)));
+    world.drain_events().count();
+    let stats = world.tick(1.0 / 60.0);
+    assert_eq!(stats.candidates, 0);
+    let phases: Vec<_> = world.drain_events().map(|it| it.phase).collect();
+    assert_eq!(phases, vec![OverlapPhase::Stay, OverlapPhase::Stay]);
+
+    let ball = world.add_body(
+        CircleObject::new_obj(5.0),
+        Vector::new(500.0, 845.0),
+        Vector::new(0.0, 50.0),
+    );
+    world.set_material(ball, material);
+    for _ in 0..10 {
+        world.tick(1.0 / 60.0);
+    }
+    assert!(boxes.iter().all(|&body| !world.is_sleeping(body)));
+
+    for _ in 0..120 {
+        world.tick(1.0 / 60.0);
+    }
+    assert!(world.is_sleeping(boxes[0]));
+    world.apply_force(boxes[0], Vector::new(10.0, 0.0));
+    assert!(boxes.iter().all(|&body| !world.is_sleeping(body)));
+}
+
+#[test]
+fn test_removing_the_floor_wakes_what_rests_on_it() {
+    let mut world = World::empty(1000.0, 1000.0);
+    world.set_gravity(Vector::new(0.0, 200.0));
+    let (floor, boxes) = stack_boxes(&mut world, 2, 880.0, 20.0);
+    for _ in 0..120 {
+        world.tick(1.0 / 60.0);
+    }
+    assert!(boxes.iter().all(|&body| world.is_sleeping(body)));
+
+    world.remove_body(floor);
+    assert!(boxes.iter().all(|&body| !world.is_sleeping(body)));
+    let before = world.position(boxes[0]).unwrap();
+    for _ in 0..10 {
+        world.tick(1.0 / 60.0);
+    }
+    assert!(world.position(boxes[0]).unwrap().y > before.y + 1.0);
+}
+
+#[test]
+fn test_moving_the_floor_wakes_what_rests_on_it() {
+    let mut world = World::empty(1000.0, 1000.0);
+    world.set_gravity(Vector::new(0.0, 200.0));
+    let (floor, boxes) = stack_boxes(&mut world, 2, 880.0, 20.0);
+    for _ in 0..120 {
+        world.tick(1.0 / 60.0);
+    }
+    assert!(boxes.iter().all(|&body| world.is_sleeping(body)));
+    world.drain_events().count();
+
+    world.set_position(floor, Vector::new(100.0, 900.0));
+    assert!(boxes.iter().all(|&body| !world.is_sleeping(body)));
+    world.tick(1.0 / 60.0);
+    let ended = world
+        .drain_events()
+        .filter(|it| it.phase == OverlapPhase::End)
+        .count();
+    assert_eq!(ended, 1);
+    let before = world.position(boxes[0]).unwrap();
+    for _ in 0..10 {
+        world.tick(1.0 / 60.0);
+    }
+    assert!(world.position(boxes[0]).unwrap().y > before.y + 1.0);
+}
+
+#[test]
+fn test_boundary_modes() {
+    let run = |boundary: Boundary| {
+        let mut world = World::empty(100.0, 100.0);
+        world.set_boundary(boundary);
+        let body = world.add_body(
+            RectangleObject::new_obj(10.0, 10.0),
+            Vector::new(96.0, 50.0),
+            Vector::new(20.0, 0.0),
+        );
+        world.tick(0.5);
+        world.tick(0.5);
+        (world, body)
+    };
+
+    let (world, body) = run(Boundary::Walls { restitution: 0.5 });
+    assert_eq!(world.speed(body), Some(Vector::new(-10.0, 0.0)));
+    assert_eq!(world.position(body), Some(Vector::new(85.0, 50.0)));
+
+    let (world, body) = run(Boundary::Wrap);
+    assert_eq!(world.position(body), Some(Vector::new(16.0, 50.0)));
+
+    let (mut world, body) = run(Boundary::Open);
+    assert_eq!(world.position(body), Some(Vector::new(116.0, 50.0)));
+    assert_eq!(world.drain_exited().count(), 0);
+
+    let (mut world, body) = run(Boundary::Kill);
+    assert!(!world.is_alive(body));
+    assert_eq!(world.drain_exited().collect::<Vec<_>>(), vec![body]);
+}
+
+#[test]
+fn test_wrapped_bodies_touch_across_the_edge() {
+    let mut world = World::empty(100.0, 100.0);
+    world.set_boundary(Boundary::Wrap);
+    let left = world.add_body(
+        CircleObject::new_obj(5.0),
+        Vector::new(2.0, 98.0),
+        Vector::zero(),
+    );
+    let right = world.add_body(
+        CircleObject::new_obj(5.0),
+        Vector::new(96.0, 2.0),
+        Vector::zero(),
+    );
+
+    world.tick(0.0);
+    let events: Vec<_> = world.drain_events().collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!((events[0].a, events[0].b), (left, right));
+    let expected = Vector::new(-6.0, 4.0).norm();
+    assert!(
+        (events[0].normal - expected).len() < 1e-9,
+        "{:?}",
+        events[0]
+    );
+
+    // Only one body of each pair sticks over the edge; for the static
+    // floor, it is the body that never looks for pairs in place.
+    for (low, high, static_low) in [(6.0, 98.0, false), (98.0, 6.0, false), (98.0, 6.0, true)] {
+        let mut world = World::empty(100.0, 100.0);
+        world.set_boundary(Boundary::Wrap);
+        let first = world.add_body(
+            CircleObject::new_obj(5.0),
+            Vector::new(50.0, low),
+            Vector::zero(),
+        );
+        let second = world.add_body(
+            CircleObject::new_obj(5.0),
+            Vector::new(50.0, high),
+            Vector::zero(),
+        );
+        if static_low {
+            world.set_body_type(first, BodyType::Static);
+        }
+
+        world.tick(0.0);
+        let events: Vec<_> = world.drain_events().collect();
+        assert_eq!(events.len(), 1, "{} {} {}", low, high, static_low);
+        assert_eq!((events[0].a, events[0].b), (first, second));
+        let expected = Vector::new(0.0, if low < high { -1.0 } else { 1.0 });
+        assert!(
+            (events[0].normal - expected).len() < 1e-9,
+            "{:?}",
+            events[0]
+        );
+    }
+}
+
+#[test]
+fn test_one_way_platform_stops_bodies_from_above_only() {
+    let run = |start: Vector, speed: Vector| {
+        let mut world = World::empty(1000.0, 1000.0);
+        world.set_gravity(Vector::new(0.0, 200.0));
+        let platform = add_static(
+            &mut world,
+            RectangleObject::new_obj(200.0, 10.0),
+            Vector::new(500.0, 500.0),
+        );
+        world.set_one_way(platform, Some(Vector::new(0.0, -1.0)));
+        let material = Material::default().with_restitution(0.0);
+        world.set_material(platform, material);
+        let ball = world.add_body(CircleObject::new_obj(10.0), start, speed);
+        world.set_material(ball, material);
+        for _ in 0..60 {
+            world.tick(1.0 / 60.0);
+        }
+        world.position(ball).unwrap()
+    };
+
+    let landed = run(Vector::new(500.0, 400.0), Vector::zero());
+    assert!((landed.y - 485.0).abs() < 1.0, "{:?}", landed);
+
+    let jumped = run(Vector::new(500.0, 600.0), Vector::new(0.0, -300.0));
+    assert!(jumped.y < 480.0, "{:?}", jumped);
+}
+
+fn lockstep_scene() -> World {
+    let mut world = World::empty(400.0, 400.0);
+    for k in 0..40 {
+        let position = Vector::new(20.0 + (k % 8) as f64 * 45.0, 20.0 + (k / 8) as f64 * 70.0);
+        let speed = Vector::new(
+            (k * 37 % 11) as f64 * 9.0 - 45.0,
+            (k * 17 % 7) as f64 * 12.0 - 36.0,
+        );
+        let shape = match k % 3 {
+            0 => RectangleObject::new_obj(25.0, 15.0),
+            1 => CircleObject::new_obj(12.0),
+            _ => PolyhedronObject::new_obj(14.0, 3 + k % 5),
+        };
+        world.add_body(shape, position, speed);
+    }
+    world
+}
+
+/// `lockstep_scene` after 200 ticks of 0.016. Peers on other platforms or
+/// builds must land on the same value; it only moves when stepping is
+/// changed on purpose.
+const LOCKSTEP_HASH: u64 = 0xe480fd1b15861f11;
+
+#[test]
+fn test_lockstep_state_hash_after_ticks() {
+    let mut a = lockstep_scene();
+    let mut b = lockstep_scene();
+    let initial = a.state_hash();
+    for _ in 0..200 {
+        a.tick(0.016);
+        b.tick(0.016);
+        assert_eq!(a.state_hash(), b.state_hash());
+    }
+    assert_ne!(a.state_hash(), initial);
+    assert_eq!(a.state_hash(), LOCKSTEP_HASH, "{:#x}", a.state_hash());
+}
+
+/// `LOCKSTEP_HASH` is pinned by the serial build, so matching it here shows
+/// the parallel path gives the same result, whatever the thread count.
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_step_matches_serial() {
+    let run = |threads: usize| {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            let mut world = lockstep_scene();
+            (0..200)
+                .map(|_| {
+                    world.tick(0.016);
+                    world.state_hash()
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let hashes = run(4);
+    assert_eq!(hashes, run(1));
+    assert_eq!(hashes.last(), Some(&LOCKSTEP_HASH));
+}
+
+#[test]
+fn test_seeded_scene_is_reproducible() {
+    let a = World::new_seeded(800.0, 600.0, 7);
+    assert_eq!(a.state_hash(), 0x49e6fc0501838dcc, "{:#x}", a.state_hash());
+    assert_eq!(
+        a.state_hash(),
+        World::new_seeded(800.0, 600.0, 7).state_hash()
+    );
+    assert_ne!(
+        a.state_hash(),
+        World::new_seeded(800.0, 600.0, 8).state_hash()
+    );
+}